@@ -0,0 +1,149 @@
+use crate::agent::{Agent, Observation};
+use crate::config::{BOT_LOOKAHEAD_TICKS, REACHABILITY_MAX_STATES, REACHABILITY_STATE_PRECISION};
+use crate::game::{TickInput, thin_out};
+use crate::player::Position;
+
+/// A bot that plans its flaps by simulating the real player physics.
+///
+/// Every tick the bot tries both inputs and follows the states reachable
+/// from each over the next `horizon` ticks, moving obstacles along and
+/// changing the scroll speed as the simulation would. Like the course
+/// generator, it follows at most [`REACHABILITY_MAX_STATES`] states per
+/// tick. It picks the input that keeps the most states alive, preferring
+/// not to flap on a tie, so it stays clear of positions it cannot recover
+/// from. If every path crashes, it picks the input that survives longest.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::agent::{Agent, LookaheadAgent, Observation};
+/// use flappycheems::game::Simulation;
+///
+/// let mut simulation = Simulation::with_seed(7);
+/// let mut agent = LookaheadAgent::new();
+///
+/// while simulation.score() < 3 {
+///     let input = agent.act(&Observation::of(&simulation));
+///     simulation.step(input);
+///     assert!(!simulation.is_game_over());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookaheadAgent {
+    horizon: u32,
+}
+
+impl LookaheadAgent {
+    pub fn new() -> Self {
+        Self::with_horizon(BOT_LOOKAHEAD_TICKS)
+    }
+
+    /// Creates a bot that looks `horizon` ticks ahead.
+    pub fn with_horizon(horizon: u32) -> Self {
+        Self {
+            horizon: horizon.max(1),
+        }
+    }
+
+    pub fn horizon(&self) -> u32 {
+        self.horizon
+    }
+
+    /// How far into the horizon the player can get if the next input is
+    /// `first`: the number of ticks survived, then the number of distinct
+    /// states still alive.
+    fn outlook(&self, observation: &Observation, first: TickInput) -> (u32, usize) {
+        let mut player = observation.player.clone();
+        let mut obstacles = observation.obstacles.clone();
+        let mut states = vec![(player.y(), player.velocity())];
+        let mut x = player.x();
+        let mut passed = 0;
+        let mut alive = 1;
+
+        for tick in 0..self.horizon {
+            for obstacle in &mut obstacles {
+                obstacle.update();
+            }
+
+            let inputs: &[bool] = if tick == 0 {
+                &[first.flap]
+            } else {
+                &[false, true]
+            };
+            let mut next = Vec::with_capacity(states.len() * 2);
+
+            for &(y, velocity) in &states {
+                for &flap in inputs {
+                    player.set_motion(Position::new(x, y), velocity);
+                    if flap {
+                        player.flap();
+                    }
+                    player.update_physics();
+
+                    let crashed = player.is_out_of_bounds()
+                        || obstacles
+                            .iter()
+                            .any(|obstacle| obstacle.has_collision_with(&player));
+                    if !crashed {
+                        next.push((
+                            state_key(player.y(), player.velocity()),
+                            player.y(),
+                            player.velocity(),
+                        ));
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                return (tick, 0);
+            }
+            next.sort_unstable_by_key(|&(key, _, _)| key);
+            next.dedup_by_key(|&mut (key, _, _)| key);
+            alive = next.len();
+
+            x = player.x();
+            let left = player.full_hitbox().left();
+            passed += obstacles
+                .iter()
+                .filter(|obstacle| obstacle.right() as f32 <= left)
+                .count();
+            obstacles.retain(|obstacle| obstacle.right() as f32 > left);
+            let speed = observation.scroll_speed(passed, observation.tick + u64::from(tick) + 1);
+            player.set_movement_speed(speed);
+            states = thin_out(&next, REACHABILITY_MAX_STATES)
+                .map(|&(_, y, velocity)| (y, velocity))
+                .collect();
+        }
+
+        (self.horizon, alive)
+    }
+}
+
+impl Default for LookaheadAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Agent for LookaheadAgent {
+    fn name(&self) -> &str {
+        "lookahead"
+    }
+
+    fn act(&mut self, observation: &Observation) -> TickInput {
+        let idle = self.outlook(observation, TickInput::IDLE);
+        let flap = self.outlook(observation, TickInput::FLAP);
+
+        if flap > idle {
+            TickInput::FLAP
+        } else {
+            TickInput::IDLE
+        }
+    }
+}
+
+/// Identifies states that behave the same from here on.
+fn state_key(y: f32, velocity: f32) -> (i64, i64) {
+    let quantize = |value: f32| (value * REACHABILITY_STATE_PRECISION).round() as i64;
+    (quantize(y), quantize(velocity))
+}
@@ -0,0 +1,9 @@
+mod lookahead;
+mod neural;
+mod observation;
+mod policy;
+
+pub use lookahead::LookaheadAgent;
+pub use neural::{GENOME_FORMAT_VERSION, Genome, NeuralAgent, default_genome_path};
+pub use observation::{Features, Observation};
+pub use policy::Agent;
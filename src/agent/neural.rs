@@ -0,0 +1,230 @@
+//! Small neural-network controllers and their genomes.
+//!
+//! A [`Genome`] holds the weights of a network with one hidden layer that
+//! maps [`Observation::features`] to a single output; the controller
+//! flaps whenever the output is positive. Genomes are evolved by
+//! [`Evolution`](crate::gym::Evolution) and saved as TOML files.
+
+use crate::agent::{Agent, Features, Observation};
+use crate::config::{BEST_GENOME_FILE_NAME, DATA_DIR_NAME, GENOME_DIR_NAME};
+use crate::error::{GameError, GameResult};
+use crate::game::TickInput;
+use bracket_lib::prelude::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the genome file format written by this build.
+pub const GENOME_FORMAT_VERSION: u32 = 1;
+
+/// The weights of a neural-network controller.
+///
+/// # Examples
+///
+/// ```
+/// use bracket_lib::prelude::RandomNumberGenerator;
+/// use flappycheems::agent::{Genome, Observation};
+/// use flappycheems::game::Simulation;
+///
+/// let mut rng = RandomNumberGenerator::seeded(3);
+/// let genome = Genome::random(8, &mut rng);
+/// assert_eq!(genome.weights.len(), Genome::weight_count(8));
+///
+/// let features = Observation::of(&Simulation::with_seed(1)).features();
+/// assert!(genome.output(&features).abs() <= 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genome {
+    /// File format version, checked when loading.
+    pub version: u32,
+    /// Number of inputs the network reads.
+    pub inputs: usize,
+    /// Number of hidden neurons.
+    pub hidden: usize,
+    /// Fitness the genome reached during training.
+    pub fitness: f32,
+    /// Generation the genome was evaluated in.
+    pub generation: u32,
+    /// Hidden-layer weights, each neuron's bias last, followed by the
+    /// output weights and bias.
+    pub weights: Vec<f32>,
+}
+
+impl Genome {
+    /// Creates a genome with `hidden` neurons and weights drawn from
+    /// `-1.0..1.0`.
+    pub fn random(hidden: usize, rng: &mut RandomNumberGenerator) -> Self {
+        let weights = (0..Self::weight_count(hidden))
+            .map(|_| rng.range(-1.0, 1.0))
+            .collect();
+
+        Self {
+            version: GENOME_FORMAT_VERSION,
+            inputs: Observation::FEATURE_COUNT,
+            hidden,
+            fitness: 0.0,
+            generation: 0,
+            weights,
+        }
+    }
+
+    /// Number of weights in a genome with `hidden` neurons.
+    pub fn weight_count(hidden: usize) -> usize {
+        hidden * (Observation::FEATURE_COUNT + 1) + hidden + 1
+    }
+
+    /// Runs the network on `features`, returning a value in `-1.0..=1.0`.
+    pub fn output(&self, features: &Features) -> f32 {
+        let per_neuron = self.inputs + 1;
+        let (hidden_weights, output_weights) = self.weights.split_at(self.hidden * per_neuron);
+
+        let activation = hidden_weights
+            .chunks_exact(per_neuron)
+            .zip(output_weights)
+            .map(|(neuron, &weight)| {
+                let (inputs, bias) = neuron.split_at(self.inputs);
+                let sum: f32 = inputs.iter().zip(features).map(|(w, x)| w * x).sum();
+                weight * (sum + bias[0]).tanh()
+            })
+            .sum::<f32>();
+
+        (activation + output_weights[self.hidden]).tanh()
+    }
+
+    /// A copy with each weight nudged by up to `strength` with chance
+    /// `rate`.
+    pub fn mutated(&self, rate: f32, strength: f32, rng: &mut RandomNumberGenerator) -> Self {
+        let mut child = self.clone();
+        for weight in &mut child.weights {
+            if rng.rand::<f32>() < rate {
+                *weight += rng.range(-strength, strength);
+            }
+        }
+        child
+    }
+
+    /// A child taking each weight from either parent at random.
+    ///
+    /// Both parents must have the same shape.
+    pub fn crossover(&self, other: &Genome, rng: &mut RandomNumberGenerator) -> Self {
+        let mut child = self.clone();
+        for (weight, &theirs) in child.weights.iter_mut().zip(&other.weights) {
+            if rng.rand::<bool>() {
+                *weight = theirs;
+            }
+        }
+        child
+    }
+
+    /// Reads a genome from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file cannot be read and
+    /// `GameError::InvalidGenome` if it is malformed, was written by an
+    /// incompatible version of the game or does not fit the network.
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        let invalid = |reason: String| GameError::InvalidGenome {
+            path: path.display().to_string(),
+            reason,
+        };
+
+        let genome: Self = toml::from_str(&contents).map_err(|e| invalid(e.to_string()))?;
+
+        if genome.version != GENOME_FORMAT_VERSION {
+            return Err(invalid(format!(
+                "unsupported format version {} (expected {})",
+                genome.version, GENOME_FORMAT_VERSION
+            )));
+        }
+
+        if genome.inputs != Observation::FEATURE_COUNT {
+            return Err(invalid(format!(
+                "network reads {} inputs (expected {})",
+                genome.inputs,
+                Observation::FEATURE_COUNT
+            )));
+        }
+
+        let expected = Self::weight_count(genome.hidden);
+        if genome.weights.len() != expected {
+            return Err(invalid(format!(
+                "{} weights for {} hidden neurons (expected {})",
+                genome.weights.len(),
+                genome.hidden,
+                expected
+            )));
+        }
+
+        Ok(genome)
+    }
+
+    /// Writes the genome to a TOML file, creating parent directories as
+    /// needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file or its directory cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> GameResult<()> {
+        let path = path.as_ref();
+        let io_error = |source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let contents = toml::to_string(self).map_err(|e| GameError::InvalidGenome {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        fs::write(path, contents).map_err(io_error)
+    }
+}
+
+/// An agent driven by an evolved [`Genome`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeuralAgent {
+    genome: Genome,
+}
+
+impl NeuralAgent {
+    pub fn new(genome: Genome) -> Self {
+        Self { genome }
+    }
+
+    pub fn genome(&self) -> &Genome {
+        &self.genome
+    }
+}
+
+impl Agent for NeuralAgent {
+    fn name(&self) -> &str {
+        "neural"
+    }
+
+    fn act(&mut self, observation: &Observation) -> TickInput {
+        TickInput {
+            flap: self.genome.output(&observation.features()) > 0.0,
+        }
+    }
+}
+
+/// Default path the trainer saves the best genome to, inside the user's
+/// data directory.
+pub fn default_genome_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| {
+        dir.join(DATA_DIR_NAME)
+            .join(GENOME_DIR_NAME)
+            .join(BEST_GENOME_FILE_NAME)
+    })
+}
@@ -0,0 +1,160 @@
+use crate::environment::{Obstacle, ObstacleGap};
+use crate::game::{DifficultyCurve, Simulation};
+use crate::player::Player;
+use std::sync::Arc;
+
+/// What an agent can see of a run at the start of a tick.
+#[derive(Debug, Clone)]
+pub struct Observation {
+    /// Ticks simulated so far.
+    pub tick: u64,
+    /// Points earned so far.
+    pub score: i32,
+    /// Obstacles cleared so far, which the difficulty follows.
+    pub passed: i32,
+    /// The player, including its physics settings.
+    pub player: Player,
+    /// Obstacles the player has not yet passed, nearest first.
+    pub obstacles: Vec<Obstacle>,
+    /// Curve setting the scroll speed as the score grows.
+    pub difficulty: Arc<dyn DifficultyCurve>,
+    /// Screen width in characters.
+    pub screen_width: i32,
+    /// Screen height in characters.
+    pub screen_height: i32,
+    /// Fastest the player can fall, in rows per tick.
+    pub max_velocity: f32,
+}
+
+/// Fixed-size numeric summary of an [`Observation`].
+pub type Features = [f32; Observation::FEATURE_COUNT];
+
+impl Observation {
+    /// Number of values in [`Observation::features`].
+    pub const FEATURE_COUNT: usize = 3 + Self::OBSTACLE_FEATURES * Self::OBSERVED_OBSTACLES;
+
+    /// Upcoming obstacles described by [`Observation::features`].
+    const OBSERVED_OBSTACLES: usize = 2;
+
+    /// Values describing each observed obstacle.
+    const OBSTACLE_FEATURES: usize = 5;
+
+    /// Captures the current state of `simulation`.
+    pub fn of(simulation: &Simulation) -> Self {
+        Self {
+            tick: simulation.tick(),
+            score: simulation.score(),
+            passed: simulation.obstacles_passed(),
+            player: simulation.player().clone(),
+            obstacles: simulation.obstacles().iter().cloned().collect(),
+            difficulty: Arc::clone(simulation.difficulty()),
+            screen_width: simulation.config().display.screen_width,
+            screen_height: simulation.config().display.screen_height,
+            max_velocity: simulation.config().physics.max_velocity,
+        }
+    }
+
+    pub fn player_y(&self) -> f32 {
+        self.player.y()
+    }
+
+    pub fn player_velocity(&self) -> f32 {
+        self.player.velocity()
+    }
+
+    /// Scroll speed after `tick` with `passed` more obstacles cleared.
+    pub fn scroll_speed(&self, passed: usize, tick: u64) -> i32 {
+        let passed = self.passed + passed as i32;
+        self.difficulty.params(passed, tick).scroll_speed.max(1)
+    }
+
+    /// Main gaps of the upcoming obstacles as they currently stand,
+    /// nearest first.
+    pub fn next_gaps(&self) -> impl Iterator<Item = ObstacleGap> + '_ {
+        self.obstacles.iter().map(Obstacle::gap)
+    }
+
+    /// The observation as a fixed-size vector of numbers, for learning
+    /// agents.
+    ///
+    /// Values are roughly in `-1.0..=1.0`:
+    ///
+    /// - player height, as a fraction of the screen height
+    /// - player velocity, as a fraction of the maximum fall speed
+    /// - scroll speed, in columns per tick
+    ///
+    /// followed by, for each of the next two obstacles, its distance
+    /// ahead as a fraction of the screen width and the top and bottom of
+    /// its two openings as fractions of the screen height. Obstacles with
+    /// one opening repeat it, and missing obstacles are reported one
+    /// screen away with the whole height open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::agent::Observation;
+    /// use flappycheems::game::Simulation;
+    ///
+    /// let features = Observation::of(&Simulation::with_seed(1)).features();
+    /// assert_eq!(features.len(), Observation::FEATURE_COUNT);
+    /// assert!(features.iter().all(|value| value.is_finite()));
+    /// ```
+    pub fn features(&self) -> Features {
+        let width = self.screen_width.max(1) as f32;
+        let height = self.screen_height.max(1) as f32;
+        let mut features = [0.0; Self::FEATURE_COUNT];
+
+        features[0] = self.player.y() / height;
+        features[1] = self.player.velocity() / self.max_velocity.max(f32::EPSILON);
+        features[2] = self.player.movement_speed() as f32;
+
+        for slot in 0..Self::OBSERVED_OBSTACLES {
+            let start = 3 + slot * Self::OBSTACLE_FEATURES;
+            let values = match self.obstacles.get(slot) {
+                Some(obstacle) => {
+                    let openings = self.openings(obstacle);
+                    let (first, second) = (openings[0], *openings.last().unwrap_or(&openings[0]));
+                    [
+                        (obstacle.x() - self.player.x()) as f32 / width,
+                        first.0 / height,
+                        first.1 / height,
+                        second.0 / height,
+                        second.1 / height,
+                    ]
+                }
+                None => [1.0, 0.0, 1.0, 0.0, 1.0],
+            };
+            features[start..start + Self::OBSTACLE_FEATURES].copy_from_slice(&values);
+        }
+
+        features
+    }
+
+    /// Open stretches of `obstacle` within the screen, top to bottom.
+    fn openings(&self, obstacle: &Obstacle) -> Vec<(f32, f32)> {
+        let height = self.screen_height as f32;
+        let mut pillars: Vec<_> = obstacle
+            .pillar_hitboxes()
+            .iter()
+            .map(|pillar| (pillar.top().max(0.0), pillar.bottom().min(height)))
+            .collect();
+        pillars.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut openings = Vec::new();
+        let mut top = 0.0;
+        for (pillar_top, pillar_bottom) in pillars {
+            if pillar_top > top {
+                openings.push((top, pillar_top));
+            }
+            top = top.max(pillar_bottom);
+        }
+        if top < height {
+            openings.push((top, height));
+        }
+
+        if openings.is_empty() {
+            openings.push((0.0, 0.0));
+        }
+        openings
+    }
+}
@@ -0,0 +1,19 @@
+use crate::agent::Observation;
+use crate::game::TickInput;
+use std::fmt::Debug;
+
+/// Something that can play the game on its own.
+///
+/// Agents see the run through an [`Observation`] once per tick and decide
+/// whether to flap. They drive the attract mode on the main menu,
+/// headless batch runs, and the autopilot and opponent in the game.
+pub trait Agent: Debug {
+    /// Short name shown in menus and reports.
+    fn name(&self) -> &str;
+
+    /// Decides the input for the next tick.
+    fn act(&mut self, observation: &Observation) -> TickInput;
+
+    /// Prepares the agent for a new run.
+    fn reset(&mut self) {}
+}
@@ -0,0 +1,124 @@
+use clap::Parser;
+use flappycheems::agent::default_genome_path;
+use flappycheems::config::GameConfig;
+use flappycheems::error::GameResult;
+use flappycheems::game::{DifficultyPreset, random_seed};
+use flappycheems::gym::{Evolution, EvolutionConfig};
+use std::path::PathBuf;
+use std::process;
+
+/// Genome file used when no data directory is available.
+const FALLBACK_GENOME_PATH: &str = "best.toml";
+
+/// Evolves neural-network controllers for Flappy Cheems.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Number of generations to evolve.
+    #[arg(long, value_name = "N", default_value_t = 50)]
+    generations: u32,
+
+    /// Controllers per generation.
+    #[arg(long, value_name = "N")]
+    population: Option<usize>,
+
+    /// Hidden neurons per controller.
+    #[arg(long, value_name = "N")]
+    hidden: Option<usize>,
+
+    /// Courses each controller plays per generation.
+    #[arg(long, value_name = "N")]
+    episodes: Option<usize>,
+
+    /// Tick limit for each course.
+    #[arg(long, value_name = "N")]
+    ticks: Option<u64>,
+
+    /// Seed for the evolution; a random seed is drawn if omitted.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Configuration file to load instead of searching the default locations.
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Difficulty preset to train on (easy, normal, hard).
+    #[arg(long, value_name = "PRESET")]
+    difficulty: Option<DifficultyPreset>,
+
+    /// Where to save the best genome; defaults to the user data directory.
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+impl Cli {
+    fn load_config(&self) -> GameResult<GameConfig> {
+        let mut config = match &self.config {
+            Some(path) => GameConfig::load(path)?,
+            None => GameConfig::discover()?,
+        };
+
+        if let Some(preset) = self.difficulty {
+            config.difficulty.preset = preset;
+        }
+
+        Ok(config)
+    }
+
+    fn settings(&self) -> EvolutionConfig {
+        let defaults = EvolutionConfig::default();
+        EvolutionConfig {
+            population: self.population.unwrap_or(defaults.population),
+            hidden: self.hidden.unwrap_or(defaults.hidden),
+            episodes: self.episodes.unwrap_or(defaults.episodes),
+            max_ticks: self.ticks.unwrap_or(defaults.max_ticks),
+            seed: self.seed.unwrap_or_else(random_seed),
+            ..defaults
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Err(e) = train(&cli) {
+        eprintln!("Training error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn train(cli: &Cli) -> GameResult<()> {
+    let output = cli
+        .output
+        .clone()
+        .or_else(default_genome_path)
+        .unwrap_or_else(|| PathBuf::from(FALLBACK_GENOME_PATH));
+    let settings = cli.settings();
+    let mut evolution = Evolution::new(cli.load_config()?, settings)?;
+
+    println!(
+        "seed={} population={} hidden={} episodes={} ticks={}",
+        settings.seed, settings.population, settings.hidden, settings.episodes, settings.max_ticks
+    );
+
+    for _ in 0..cli.generations {
+        let report = evolution.evolve();
+        println!(
+            "generation={} best_fitness={:.2} mean_fitness={:.2} worst_fitness={:.2} best_score={}",
+            report.generation,
+            report.best_fitness,
+            report.mean_fitness,
+            report.worst_fitness,
+            report.best_score
+        );
+
+        if report.improved
+            && let Some(champion) = evolution.champion()
+        {
+            champion.save(&output)?;
+            println!("saved best genome to {}", output.display());
+        }
+    }
+
+    Ok(())
+}
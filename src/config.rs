@@ -3,6 +3,13 @@
 //! This module centralizes all game constants, physics parameters, and configuration
 //! settings to ensure consistency and easy tuning of game behavior.
 
+use crate::error::{GameError, GameResult};
+use crate::game::{CurveShape, DifficultyCurve, DifficultyPreset, PlayMode};
+use crate::player::Skin;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
 // Display Settings
@@ -15,6 +22,15 @@ pub const SCREEN_HEIGHT: i32 = 30;
 /// Duration between physics updates in milliseconds.
 pub const FRAME_DURATION_MS: f32 = 75.0;
 
+/// Length of the countdown shown when resuming a paused game, in milliseconds.
+pub const RESUME_COUNTDOWN_MS: f32 = 1500.0;
+
+/// Number of steps the resume countdown is divided into.
+pub const RESUME_COUNTDOWN_STEPS: i32 = 3;
+
+/// Opacity of the ghost sprite, from 0.0 (invisible) to 1.0 (solid).
+pub const GHOST_OPACITY: f32 = 0.4;
+
 /// Size of sprite tiles in pixels.
 pub const TILE_SIZE: i32 = 32;
 
@@ -34,6 +50,12 @@ pub const PHYSICS_FLAP_STRENGTH: f32 = -1.0;
 /// Horizontal movement speed of the player in units per frame.
 pub const PLAYER_MOVEMENT_SPEED: i32 = 1;
 
+/// Horizontal starting position of the player.
+pub const PLAYER_START_X: i32 = 5;
+
+/// Vertical starting position of the player.
+pub const PLAYER_START_Y: i32 = 25;
+
 // Obstacle Configuration
 /// Minimum gap size between obstacle segments.
 pub const OBSTACLE_MIN_GAP_SIZE: i32 = 2;
@@ -47,6 +69,123 @@ pub const OBSTACLE_GAP_MIN_Y: i32 = 5;
 /// Maximum Y coordinate for obstacle gap center.
 pub const OBSTACLE_GAP_MAX_Y: i32 = 20;
 
+/// Horizontal distance between consecutive obstacles.
+pub const OBSTACLE_SPACING: i32 = 20;
+
+/// Width of each obstacle in columns.
+pub const OBSTACLE_WIDTH: i32 = 1;
+
+// Obstacle Variants
+/// Gap size used by moving and pillar obstacles instead of the shrinking gap.
+pub const OBSTACLE_VARIANT_GAP_SIZE: i32 = 6;
+
+/// Rows an oscillating gap drifts above and below its starting center.
+pub const OBSTACLE_OSCILLATION_AMPLITUDE: i32 = 3;
+
+/// Ticks an oscillating gap takes to drift up, down and back.
+pub const OBSTACLE_OSCILLATION_PERIOD: u32 = 40;
+
+/// Ticks between each row a closing gap narrows by.
+pub const OBSTACLE_CLOSING_INTERVAL: u32 = 15;
+
+/// Rows of pillar separating the two gaps of a double-gap obstacle.
+pub const OBSTACLE_DOUBLE_GAP_SEPARATION: i32 = 3;
+
+/// Random obstacles rolled before an unreachable one is adjusted instead.
+pub const OBSTACLE_GENERATION_ATTEMPTS: u32 = 8;
+
+/// Player states closer than one part in this many rows or rows per tick
+/// are treated as the same state when checking reachability.
+pub const REACHABILITY_STATE_PRECISION: f32 = 1000.0;
+
+/// Most player states followed per tick when checking reachability; beyond
+/// this an evenly spread subset is kept.
+pub const REACHABILITY_MAX_STATES: usize = 256;
+
+// Power-ups
+/// Chance, in percent, of a pickup being placed in an obstacle's gap.
+pub const POWER_UP_SPAWN_CHANCE: u32 = 20;
+
+/// Ticks a collected power-up lasts for.
+pub const POWER_UP_DURATION_TICKS: u32 = 100;
+
+/// Factor the frame duration is stretched by during slow motion.
+pub const POWER_UP_SLOW_MOTION_FACTOR: f32 = 2.0;
+
+/// Factor the player's hitbox and sprite are scaled by while shrunk.
+pub const POWER_UP_SHRINK_SCALE: f32 = 0.5;
+
+/// Points each obstacle is worth while the score multiplier lasts.
+pub const POWER_UP_SCORE_MULTIPLIER: i32 = 2;
+
+// Agent Settings
+/// Ticks the lookahead bot simulates before choosing an input.
+pub const BOT_LOOKAHEAD_TICKS: u32 = 40;
+
+// Training Settings
+/// Default reward for every tick a training episode survives.
+pub const REWARD_SURVIVAL: f32 = 0.1;
+
+/// Default reward for every obstacle cleared in a training episode.
+pub const REWARD_OBSTACLE_PASSED: f32 = 1.0;
+
+/// Default penalty per screen height of distance from the next gap center.
+pub const REWARD_GAP_DISTANCE: f32 = 0.1;
+
+/// Default reward for crashing in a training episode.
+pub const REWARD_CRASH: f32 = -1.0;
+
+/// Neural-network controllers evolved in each generation.
+pub const TRAIN_POPULATION: usize = 50;
+
+/// Hidden neurons in an evolved controller.
+pub const TRAIN_HIDDEN_NEURONS: usize = 8;
+
+/// Best controllers copied unchanged into the next generation.
+pub const TRAIN_ELITE: usize = 4;
+
+/// Controllers compared when picking each parent.
+pub const TRAIN_TOURNAMENT_SIZE: usize = 3;
+
+/// Chance of each weight being nudged when a controller is mutated.
+pub const TRAIN_MUTATION_RATE: f32 = 0.1;
+
+/// Largest nudge a mutation applies to a weight.
+pub const TRAIN_MUTATION_STRENGTH: f32 = 0.5;
+
+/// Courses every controller plays per generation.
+pub const TRAIN_EPISODES: usize = 3;
+
+/// Tick limit for each training episode.
+pub const TRAIN_MAX_TICKS: u64 = 2_000;
+
+/// Fewest environments a [`VecEnv`](crate::gym::VecEnv) gives each worker
+/// thread; smaller batches are stepped on the calling thread.
+pub const VEC_ENV_MIN_ENVS_PER_THREAD: usize = 16;
+
+// Network Settings
+/// Longest wait for the other side during the lobby handshake, in milliseconds.
+pub const NET_HANDSHAKE_TIMEOUT_MS: u64 = 10_000;
+
+/// Silence after which a network peer is considered disconnected, in milliseconds.
+pub const NET_PEER_TIMEOUT_MS: u64 = 5_000;
+
+/// Longest gap between messages sent to a network peer, in milliseconds.
+pub const NET_PING_INTERVAL_MS: u64 = 1_000;
+
+/// Most bytes queued for a spectator before it is dropped for falling behind.
+pub const NET_SPECTATOR_MAX_BACKLOG_BYTES: usize = 256 * 1024;
+
+// Difficulty Settings
+/// Number of plateaus a stepped difficulty curve climbs through.
+pub const DIFFICULTY_STEPPED_STEPS: i32 = 4;
+
+/// Steepness of the logistic difficulty curve across its ramp.
+pub const DIFFICULTY_LOGISTIC_STEEPNESS: f32 = 8.0;
+
+/// Ticks that add one level of difficulty on the hard preset.
+pub const DIFFICULTY_HARD_TICKS_PER_LEVEL: u64 = 200;
+
 // Animation Settings
 /// Sprite indices for Cheems character animation frames.
 pub const CHEEMS_ANIMATION_FRAMES: [u16; 6] = [64, 1, 2, 3, 2, 1];
@@ -54,37 +193,720 @@ pub const CHEEMS_ANIMATION_FRAMES: [u16; 6] = [64, 1, 2, 3, 2, 1];
 /// Total number of animation frames for the Cheems character.
 pub const CHEEMS_ANIMATION_FRAME_COUNT: usize = 6;
 
-// Asset Paths
-/// Path to the font sprite sheet.
-pub const ASSET_PATH_FONT: &str = "../resources/flappycheems.png";
+/// Scale at which the character sprite is drawn, in console cells.
+pub const PLAYER_RENDER_SCALE: f32 = 2.0;
+
+/// Width and height of the player's hitbox, in console cells.
+pub const PLAYER_HITBOX_SIZE: f32 = 2.0;
+
+/// Name of the character played when no other is picked.
+pub const DEFAULT_SKIN_NAME: &str = "Cheems";
+
+/// Ticks each animation frame stays on screen by default.
+pub const DEFAULT_SKIN_FRAME_TICKS: u32 = 1;
+
+/// Glyphs per row of a sprite sheet.
+pub const SPRITE_SHEET_COLUMNS: u16 = 16;
+
+/// Rows of glyphs in a sprite sheet.
+pub const SPRITE_SHEET_ROWS: u16 = 16;
 
-/// Path to the game sprite sheet.
-pub const ASSET_PATH_SPRITES: &str = "../resources/flappycheems.png";
+/// Glyph the player is drawn as in terminal builds, which cannot draw sprites.
+pub const PLAYER_TERMINAL_GLYPH: char = '@';
+
+// Assets
+/// Directory the sprite sheets embedded in the binary are registered under.
+pub const ASSET_EMBED_DIR: &str = "resources";
+
+/// File name of the font sprite sheet.
+pub const ASSET_FILE_FONT: &str = "flappycheems.png";
+
+/// File name of the game sprite sheet.
+pub const ASSET_FILE_SPRITES: &str = "flappycheems.png";
+
+// Storage
+/// Name of the game's directory inside the user data directory.
+pub const DATA_DIR_NAME: &str = "flappycheems";
+
+/// Name of the replay directory inside the game's data directory.
+pub const REPLAY_DIR_NAME: &str = "replays";
+
+/// Name of the directory trained genomes are saved to, inside the data directory.
+pub const GENOME_DIR_NAME: &str = "genomes";
+
+/// File name the trainer saves the best genome under.
+pub const BEST_GENOME_FILE_NAME: &str = "best.toml";
+
+/// Name of the high-score file inside the game's data directory.
+pub const HIGH_SCORE_FILE_NAME: &str = "high_scores.toml";
+
+/// Number of entries kept in the high-score table.
+pub const HIGH_SCORE_CAPACITY: usize = 10;
+
+/// Maximum length of a name entered for the high-score table.
+pub const HIGH_SCORE_NAME_MAX_LEN: usize = 12;
+
+/// File name of the game's TOML configuration file.
+pub const CONFIG_FILE_NAME: &str = "flappycheems.toml";
+
+/// File name of the character registry, searched like the configuration file.
+pub const SKINS_FILE_NAME: &str = "skins.toml";
 
 // Game Metadata
 /// Display title for the game window.
 pub const GAME_TITLE: &str = "Flappy Cheems Adventure";
 
-/// Configuration structure for game settings.
+/// Runtime configuration for the whole game.
 ///
-/// Provides a centralized way to manage game configuration with
-/// sensible defaults and type safety.
-#[derive(Debug, Clone, Copy)]
+/// Every field defaults to the matching constant in this module, so a
+/// configuration file only needs to list the values it changes:
+///
+/// ```toml
+/// mode = "practice"
+///
+/// [physics]
+/// gravity = 0.15
+///
+/// [obstacle]
+/// base_gap_size = 12
+///
+/// [difficulty]
+/// preset = "hard"
+/// curve = "logistic"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct GameConfig {
+    /// Rule set runs are played under.
+    pub mode: PlayMode,
+    /// Window and timing settings.
+    pub display: DisplayConfig,
+    /// Player movement and gravity settings.
+    pub physics: PhysicsConfig,
+    /// Obstacle layout settings.
+    pub obstacle: ObstacleConfig,
+    /// Difficulty progression settings.
+    pub difficulty: DifficultyConfig,
+    /// Pickup spawning and effect settings.
+    pub power_ups: PowerUpConfig,
+    /// Character the player flies as. Older files call this `animation`.
+    ///
+    /// Only changes how the player looks, so like the asset directory it
+    /// stays on this machine and two configurations that differ only in
+    /// their skin are equal.
+    #[serde(alias = "animation", skip_serializing)]
+    pub skin: Skin,
+    /// Asset file locations.
+    pub assets: AssetConfig,
+}
+
+impl GameConfig {
+    /// Parses a configuration from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::InvalidConfig` if the text is not valid TOML or
+    /// contains unknown or mistyped settings. `origin` names the source of
+    /// the text in the error message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::config::GameConfig;
+    ///
+    /// let config = GameConfig::from_toml_str("[physics]\ngravity = 0.1\n", "inline")?;
+    /// assert_eq!(config.physics.gravity, 0.1);
+    /// assert_eq!(config.display, GameConfig::default().display);
+    /// # Ok::<(), flappycheems::error::GameError>(())
+    /// ```
+    pub fn from_toml_str(contents: &str, origin: &str) -> GameResult<Self> {
+        let config: Self = toml::from_str(contents).map_err(|e| GameError::InvalidConfig {
+            path: origin.to_string(),
+            reason: e.to_string(),
+        })?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the settings describe a playable game.
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - `GameError::InvalidScreenDimensions` if the screen is not positive in size
+    /// - `GameError::InvalidFrameDuration` if updates have no usable interval
+    /// - `GameError::InvalidTileSize` if sprite or console tiles have no size
+    /// - `GameError::PlayerOutOfBounds` if the player would start off screen
+    /// - `GameError::InvalidObstacleGapRange` if gap centers do not fit on screen
+    /// - `GameError::InvalidObstacleGapSize` if the minimum gap exceeds the base gap
+    /// - `GameError::InvalidObstacleSpacing` if obstacles overlap or have no width
+    /// - `GameError::InvalidObstacleVariants` if a moving or double-gap obstacle cannot work
+    /// - `GameError::InvalidPhysics` if gravity, the velocity limit or the hitbox size is not positive
+    /// - `GameError::InvalidMovementSpeed` if the player would not move forward
+    /// - `GameError::FlapTooWeak` if flapping cannot lift the player
+    /// - `GameError::InvalidPowerUps` if a power-up has no effect or breaks the game
+    /// - `GameError::InvalidSkin` if the character cannot be drawn
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::config::GameConfig;
+    /// use flappycheems::error::GameError;
+    ///
+    /// let mut config = GameConfig::default();
+    /// assert!(config.validate().is_ok());
+    ///
+    /// config.display.screen_height = 0;
+    /// assert!(matches!(
+    ///     config.validate(),
+    ///     Err(GameError::InvalidScreenDimensions { height: 0, .. })
+    /// ));
+    ///
+    /// let mut config = GameConfig::default();
+    /// config.physics.max_velocity = f32::NAN;
+    /// assert!(matches!(config.validate(), Err(GameError::InvalidPhysics { .. })));
+    ///
+    /// let mut config = GameConfig::default();
+    /// config.physics.movement_speed = 0;
+    /// assert!(matches!(
+    ///     config.validate(),
+    ///     Err(GameError::InvalidMovementSpeed { movement_speed: 0 })
+    /// ));
+    /// ```
+    pub fn validate(&self) -> GameResult<()> {
+        let display = &self.display;
+        let physics = &self.physics;
+        let obstacle = &self.obstacle;
+
+        if display.screen_width <= 0 || display.screen_height <= 0 {
+            return Err(GameError::InvalidScreenDimensions {
+                width: display.screen_width,
+                height: display.screen_height,
+            });
+        }
+
+        let frame_duration_ms = display.frame_duration_ms;
+        if !frame_duration_ms.is_finite()
+            || frame_duration_ms <= 0.0
+            || Duration::try_from_secs_f32(frame_duration_ms / 1000.0).is_err()
+        {
+            return Err(GameError::InvalidFrameDuration { frame_duration_ms });
+        }
+
+        if display.tile_size <= 0 || display.console_tile_size <= 0 {
+            return Err(GameError::InvalidTileSize {
+                tile_size: display.tile_size,
+                console_tile_size: display.console_tile_size,
+            });
+        }
+
+        if !(0..display.screen_width).contains(&physics.start_x)
+            || !(0..display.screen_height).contains(&physics.start_y)
+        {
+            return Err(GameError::PlayerOutOfBounds {
+                x: physics.start_x,
+                y: physics.start_y as f32,
+            });
+        }
+
+        if obstacle.gap_min_y < 0
+            || obstacle.gap_min_y >= obstacle.gap_max_y
+            || obstacle.gap_max_y > display.screen_height
+        {
+            return Err(GameError::InvalidObstacleGapRange {
+                min_y: obstacle.gap_min_y,
+                max_y: obstacle.gap_max_y,
+                screen_height: display.screen_height,
+            });
+        }
+
+        if obstacle.min_gap_size <= 0 || obstacle.min_gap_size > obstacle.base_gap_size {
+            return Err(GameError::InvalidObstacleGapSize {
+                min_gap_size: obstacle.min_gap_size,
+                base_gap_size: obstacle.base_gap_size,
+            });
+        }
+
+        if obstacle.spacing <= 0 || obstacle.width <= 0 || obstacle.width > obstacle.spacing {
+            return Err(GameError::InvalidObstacleSpacing {
+                spacing: obstacle.spacing,
+                width: obstacle.width,
+            });
+        }
+
+        obstacle.validate_variants()?;
+
+        if !physics.gravity.is_finite()
+            || physics.gravity <= 0.0
+            || !physics.max_velocity.is_finite()
+            || physics.max_velocity <= 0.0
+            || !physics.hitbox_size.is_finite()
+            || physics.hitbox_size <= 0.0
+        {
+            return Err(GameError::InvalidPhysics {
+                gravity: physics.gravity,
+                max_velocity: physics.max_velocity,
+                hitbox_size: physics.hitbox_size,
+            });
+        }
+
+        // Any speed above this is safe: collisions sweep the hitbox along
+        // each move, so even a shrunk player cannot skip a pillar.
+        if physics.movement_speed < 1 {
+            return Err(GameError::InvalidMovementSpeed {
+                movement_speed: physics.movement_speed,
+            });
+        }
+
+        let flap_velocity = physics
+            .flap_strength
+            .clamp(-physics.max_velocity * 2.0, physics.max_velocity);
+        if !flap_velocity.is_finite() || flap_velocity + physics.gravity >= 0.0 {
+            return Err(GameError::FlapTooWeak {
+                flap_strength: flap_velocity,
+                gravity: physics.gravity,
+            });
+        }
+
+        self.power_ups.validate()?;
+        self.skin.validate()
+    }
+
+    /// Builds the difficulty curve selected by the `difficulty` settings.
+    pub fn difficulty_curve(&self) -> Arc<dyn DifficultyCurve> {
+        self.difficulty
+            .curve
+            .build(self.difficulty.preset.bounds(self))
+    }
+
+    /// Takes over the settings of `local` that only concern this machine,
+    /// the skin and the asset directory, keeping everything that changes
+    /// how the game plays.
+    ///
+    /// Used when the configuration comes from elsewhere, such as a replay
+    /// or the host of a network race.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::config::GameConfig;
+    ///
+    /// let mut local = GameConfig::default();
+    /// local.assets.dir = Some("sheets".into());
+    /// local.skin.name = "Doge".to_string();
+    ///
+    /// let mut received = GameConfig::default();
+    /// received.physics.gravity = 0.3;
+    /// received.apply_local(&local);
+    /// assert_eq!(received.assets.dir, local.assets.dir);
+    /// assert_eq!(received.skin.name, "Doge");
+    /// assert_eq!(received.physics.gravity, 0.3);
+    /// ```
+    pub fn apply_local(&mut self, local: &GameConfig) {
+        self.skin.clone_from(&local.skin);
+        self.assets.dir.clone_from(&local.assets.dir);
+    }
+
+    /// Loads a configuration from a TOML file.
+    ///
+    /// A relative `[assets] dir` is taken relative to the directory the
+    /// file is in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file cannot be read and
+    /// `GameError::InvalidConfig` if its contents are malformed.
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        let mut config = Self::from_toml_str(&contents, &path.display().to_string())?;
+        if let (Some(dir), Some(parent)) = (&mut config.assets.dir, path.parent())
+            && dir.is_relative()
+        {
+            *dir = parent.join(&*dir);
+        }
+        Ok(config)
+    }
+
+    /// Loads the first configuration file found in the standard locations.
+    ///
+    /// The user config directory is searched first, then the current
+    /// working directory. If neither contains a `flappycheems.toml`, the
+    /// default configuration is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration file exists but cannot be loaded.
+    pub fn discover() -> GameResult<Self> {
+        match Self::search_paths().into_iter().find(|path| path.is_file()) {
+            Some(path) => Self::load(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Locations searched by [`GameConfig::discover`], in priority order.
+    pub fn search_paths() -> Vec<PathBuf> {
+        let user_config =
+            dirs::config_dir().map(|dir| dir.join(DATA_DIR_NAME).join(CONFIG_FILE_NAME));
+
+        user_config
+            .into_iter()
+            .chain(Some(PathBuf::from(CONFIG_FILE_NAME)))
+            .collect()
+    }
+}
+
+impl PartialEq for GameConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.mode == other.mode
+            && self.display == other.display
+            && self.physics == other.physics
+            && self.obstacle == other.obstacle
+            && self.difficulty == other.difficulty
+            && self.power_ups == other.power_ups
+            && self.assets == other.assets
+    }
+}
+
+/// Window and timing settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
     /// Screen width in characters.
     pub screen_width: i32,
     /// Screen height in characters.
     pub screen_height: i32,
-    /// Frame duration for physics updates.
-    pub frame_duration: Duration,
+    /// Duration between physics updates in milliseconds.
+    pub frame_duration_ms: f32,
+    /// Size of sprite tiles in pixels.
+    pub tile_size: i32,
+    /// Size of console tiles for rendering.
+    pub console_tile_size: i32,
+    /// Display title for the game window.
+    pub title: String,
 }
 
-impl Default for GameConfig {
+impl DisplayConfig {
+    /// Duration between physics updates.
+    pub fn frame_duration(&self) -> Duration {
+        Duration::from_secs_f32(self.frame_duration_ms / 1000.0)
+    }
+}
+
+impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
             screen_width: SCREEN_WIDTH,
             screen_height: SCREEN_HEIGHT,
-            frame_duration: Duration::from_millis(FRAME_DURATION_MS as u64),
+            frame_duration_ms: FRAME_DURATION_MS,
+            tile_size: TILE_SIZE,
+            console_tile_size: CONSOLE_TILE_SIZE,
+            title: GAME_TITLE.to_string(),
+        }
+    }
+}
+
+/// Player movement and gravity settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicsConfig {
+    /// Gravitational acceleration applied to the player each frame.
+    pub gravity: f32,
+    /// Maximum downward velocity the player can achieve.
+    pub max_velocity: f32,
+    /// Upward velocity applied when the player flaps.
+    pub flap_strength: f32,
+    /// Horizontal movement speed of the player in units per frame.
+    pub movement_speed: i32,
+    /// Horizontal starting position of the player.
+    pub start_x: i32,
+    /// Vertical starting position of the player.
+    pub start_y: i32,
+    /// Width and height of the player's hitbox, whatever the character.
+    pub hitbox_size: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            gravity: PHYSICS_GRAVITY,
+            max_velocity: PHYSICS_MAX_VELOCITY,
+            flap_strength: PHYSICS_FLAP_STRENGTH,
+            movement_speed: PLAYER_MOVEMENT_SPEED,
+            start_x: PLAYER_START_X,
+            start_y: PLAYER_START_Y,
+            hitbox_size: PLAYER_HITBOX_SIZE,
+        }
+    }
+}
+
+/// Obstacle layout settings.
+///
+/// These are the baseline the difficulty presets are derived from; the
+/// normal preset uses them as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObstacleConfig {
+    /// Minimum gap size between obstacle segments.
+    pub min_gap_size: i32,
+    /// Gap size at the start of a run on the normal preset.
+    pub base_gap_size: i32,
+    /// Minimum Y coordinate for obstacle gap center.
+    pub gap_min_y: i32,
+    /// Maximum Y coordinate for obstacle gap center.
+    pub gap_max_y: i32,
+    /// Horizontal distance between consecutive obstacles.
+    pub spacing: i32,
+    /// Width of each obstacle in columns.
+    pub width: i32,
+    /// Gap size used by moving and pillar obstacles instead of the
+    /// shrinking gap.
+    pub variant_gap_size: i32,
+    /// Rows an oscillating gap drifts above and below its starting center.
+    pub oscillation_amplitude: i32,
+    /// Ticks an oscillating gap takes to drift up, down and back.
+    pub oscillation_period: u32,
+    /// Ticks between each row a closing gap narrows by.
+    pub closing_interval: u32,
+    /// Rows of pillar separating the two gaps of a double-gap obstacle.
+    pub double_gap_separation: i32,
+}
+
+impl ObstacleConfig {
+    /// Checks the settings of the moving and multi-gap obstacle kinds.
+    fn validate_variants(&self) -> GameResult<()> {
+        let invalid = |reason: String| Err(GameError::InvalidObstacleVariants { reason });
+
+        if self.variant_gap_size < self.min_gap_size {
+            return invalid(format!(
+                "variant gap size {} is below the minimum gap size {}",
+                self.variant_gap_size, self.min_gap_size
+            ));
+        }
+        if self.oscillation_amplitude < 0 {
+            return invalid(format!(
+                "oscillation amplitude {} is negative",
+                self.oscillation_amplitude
+            ));
+        }
+        if self.oscillation_period == 0 {
+            return invalid("oscillation period must be at least one tick".to_string());
+        }
+        if self.closing_interval == 0 {
+            return invalid("closing interval must be at least one tick".to_string());
+        }
+        if self.double_gap_separation <= 0 {
+            return invalid(format!(
+                "double gap separation {} leaves no pillar between the gaps",
+                self.double_gap_separation
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ObstacleConfig {
+    fn default() -> Self {
+        Self {
+            min_gap_size: OBSTACLE_MIN_GAP_SIZE,
+            base_gap_size: OBSTACLE_BASE_GAP_SIZE,
+            gap_min_y: OBSTACLE_GAP_MIN_Y,
+            gap_max_y: OBSTACLE_GAP_MAX_Y,
+            spacing: OBSTACLE_SPACING,
+            width: OBSTACLE_WIDTH,
+            variant_gap_size: OBSTACLE_VARIANT_GAP_SIZE,
+            oscillation_amplitude: OBSTACLE_OSCILLATION_AMPLITUDE,
+            oscillation_period: OBSTACLE_OSCILLATION_PERIOD,
+            closing_interval: OBSTACLE_CLOSING_INTERVAL,
+            double_gap_separation: OBSTACLE_DOUBLE_GAP_SEPARATION,
+        }
+    }
+}
+
+/// Difficulty progression settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DifficultyConfig {
+    /// How hard the course starts and ends up.
+    pub preset: DifficultyPreset,
+    /// How quickly the course moves from its start to its end settings.
+    pub curve: CurveShape,
+}
+
+/// Pickup spawning and effect settings.
+///
+/// Setting `spawn_chance` to zero plays without power-ups.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PowerUpConfig {
+    /// Chance, in percent, of a pickup being placed in an obstacle's gap.
+    pub spawn_chance: u32,
+    /// Ticks a collected power-up lasts for.
+    pub duration_ticks: u32,
+    /// Factor the frame duration is stretched by during slow motion.
+    pub slow_motion_factor: f32,
+    /// Factor the player's hitbox and sprite are scaled by while shrunk.
+    pub shrink_scale: f32,
+    /// Points each obstacle is worth while the score multiplier lasts.
+    pub score_multiplier: i32,
+}
+
+impl PowerUpConfig {
+    /// Checks that every power-up helps the player without breaking the game.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::InvalidPowerUps` if the spawn chance exceeds
+    /// 100%, effects last no ticks, slow motion speeds the game up, shrink
+    /// grows or vanishes the player, or the multiplier is below one.
+    pub fn validate(&self) -> GameResult<()> {
+        let invalid = |reason: String| Err(GameError::InvalidPowerUps { reason });
+
+        if self.spawn_chance > 100 {
+            return invalid(format!("spawn chance {}% exceeds 100%", self.spawn_chance));
+        }
+        if self.duration_ticks == 0 {
+            return invalid("effects must last at least one tick".to_string());
+        }
+        if !self.slow_motion_factor.is_finite() || self.slow_motion_factor < 1.0 {
+            return invalid(format!(
+                "slow motion factor {} is below 1",
+                self.slow_motion_factor
+            ));
+        }
+        if !self.shrink_scale.is_finite() || self.shrink_scale <= 0.0 || self.shrink_scale > 1.0 {
+            return invalid(format!(
+                "shrink scale {} is not between 0 and 1",
+                self.shrink_scale
+            ));
+        }
+        if self.score_multiplier < 1 {
+            return invalid(format!(
+                "score multiplier {} is below 1",
+                self.score_multiplier
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether pickups are placed on the course at all.
+    pub fn enabled(&self) -> bool {
+        self.spawn_chance > 0
+    }
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        Self {
+            spawn_chance: POWER_UP_SPAWN_CHANCE,
+            duration_ticks: POWER_UP_DURATION_TICKS,
+            slow_motion_factor: POWER_UP_SLOW_MOTION_FACTOR,
+            shrink_scale: POWER_UP_SHRINK_SCALE,
+            score_multiplier: POWER_UP_SCORE_MULTIPLIER,
+        }
+    }
+}
+
+/// Asset file locations.
+///
+/// The sprite sheets ship inside the binary; setting `dir` loads them from
+/// that directory instead, for example to try out a new sheet. The
+/// directory only makes sense on this machine, so it is never written to
+/// replays or sent to other games, and configurations that differ only in
+/// it compare equal.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AssetConfig {
+    /// Directory to load the sprite sheets from instead of the embedded ones.
+    #[serde(skip_serializing)]
+    pub dir: Option<PathBuf>,
+    /// File name of the font sprite sheet.
+    pub font: String,
+    /// File name of the game sprite sheet.
+    pub sprites: String,
+}
+
+impl AssetConfig {
+    /// Directory bracket-lib should load the sprite sheets from.
+    ///
+    /// Without an override this is the directory the embedded sheets are
+    /// registered under. An override is made absolute, so that it can
+    /// never be mistaken for the embedded copies.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::AssetLoadingFailed` with the full path of the
+    /// first sheet that is missing from the override directory, or that
+    /// is not embedded when there is no override.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::config::{ASSET_EMBED_DIR, AssetConfig};
+    /// use flappycheems::error::GameError;
+    ///
+    /// let mut assets = AssetConfig::default();
+    /// assert_eq!(assets.resource_dir()?, ASSET_EMBED_DIR);
+    ///
+    /// assets.dir = Some("no/such/dir".into());
+    /// let Err(GameError::AssetLoadingFailed { path }) = assets.resource_dir() else {
+    ///     panic!("missing sheet was not reported");
+    /// };
+    /// assert!(path.ends_with("no/such/dir/flappycheems.png"));
+    /// # Ok::<(), GameError>(())
+    /// ```
+    pub fn resource_dir(&self) -> GameResult<String> {
+        let Some(dir) = &self.dir else {
+            let embedded = [ASSET_FILE_FONT, ASSET_FILE_SPRITES];
+            if let Some(file) = self.files().find(|file| !embedded.contains(file)) {
+                return Err(GameError::AssetLoadingFailed {
+                    path: Path::new(ASSET_EMBED_DIR).join(file).display().to_string(),
+                });
+            }
+            return Ok(ASSET_EMBED_DIR.to_string());
+        };
+
+        let dir = std::path::absolute(dir).map_err(|e| GameError::Io {
+            path: dir.display().to_string(),
+            source: e,
+        })?;
+        if let Some(path) = self
+            .files()
+            .map(|file| dir.join(file))
+            .find(|path| !path.is_file())
+        {
+            return Err(GameError::AssetLoadingFailed {
+                path: path.display().to_string(),
+            });
+        }
+
+        Ok(dir.display().to_string())
+    }
+
+    fn files(&self) -> impl Iterator<Item = &str> {
+        [self.font.as_str(), self.sprites.as_str()].into_iter()
+    }
+}
+
+impl PartialEq for AssetConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.font == other.font && self.sprites == other.sprites
+    }
+}
+
+impl Default for AssetConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            font: ASSET_FILE_FONT.to_string(),
+            sprites: ASSET_FILE_SPRITES.to_string(),
         }
     }
 }
@@ -0,0 +1,139 @@
+/// An axis-aligned rectangle in world coordinates used for collision tests.
+///
+/// Edges are half-open: a box covers `left..right` horizontally and
+/// `top..bottom` vertically, so boxes that merely touch do not overlap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hitbox {
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32,
+}
+
+impl Hitbox {
+    /// Creates a box from its top-left corner and size.
+    pub fn new(left: f32, top: f32, width: f32, height: f32) -> Self {
+        Self {
+            left,
+            top,
+            right: left + width,
+            bottom: top + height,
+        }
+    }
+
+    /// Creates a box of the given size centred on a point.
+    pub fn centered(center_x: f32, center_y: f32, width: f32, height: f32) -> Self {
+        Self::new(
+            center_x - width / 2.0,
+            center_y - height / 2.0,
+            width,
+            height,
+        )
+    }
+
+    /// Creates a box from its edges.
+    pub fn from_edges(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub fn left(&self) -> f32 {
+        self.left
+    }
+
+    pub fn top(&self) -> f32 {
+        self.top
+    }
+
+    pub fn right(&self) -> f32 {
+        self.right
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.bottom
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    /// Returns `true` if the two boxes share any area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::environment::Hitbox;
+    ///
+    /// let player = Hitbox::new(0.0, 4.5, 2.0, 2.0);
+    ///
+    /// assert!(player.overlaps(&Hitbox::new(1.5, 0.0, 1.0, 5.0)));
+    /// assert!(!player.overlaps(&Hitbox::new(2.0, 0.0, 1.0, 5.0)));
+    /// ```
+    pub fn overlaps(&self, other: &Hitbox) -> bool {
+        self.left < other.right
+            && other.left < self.right
+            && self.top < other.bottom
+            && other.top < self.bottom
+    }
+
+    /// Returns `true` if the box, moved in a straight line by `dx` and
+    /// `dy`, overlaps `other` at any point of the move, its start and end
+    /// included.
+    ///
+    /// Fast boxes cannot skip over thin ones this way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::environment::Hitbox;
+    ///
+    /// let player = Hitbox::new(0.0, 4.0, 1.0, 1.0);
+    /// let pillar = Hitbox::new(1.0, 0.0, 1.0, 10.0);
+    ///
+    /// assert!(!player.overlaps(&pillar));
+    /// assert!(player.overlaps_along(2.0, 0.0, &pillar));
+    /// assert!(!player.overlaps_along(2.0, 10.0, &Hitbox::new(1.0, 0.0, 1.0, 4.0)));
+    /// ```
+    pub fn overlaps_along(&self, dx: f32, dy: f32, other: &Hitbox) -> bool {
+        let (enter_x, exit_x) = overlap_times(self.left, self.right, dx, other.left, other.right);
+        let (enter_y, exit_y) = overlap_times(self.top, self.bottom, dy, other.top, other.bottom);
+        let enter = enter_x.max(enter_y);
+        let exit = exit_x.min(exit_y);
+
+        enter < exit && enter < 1.0 && exit > 0.0
+    }
+
+    /// The smallest box covering both boxes.
+    pub fn union(&self, other: &Hitbox) -> Hitbox {
+        Self::from_edges(
+            self.left.min(other.left),
+            self.top.min(other.top),
+            self.right.max(other.right),
+            self.bottom.max(other.bottom),
+        )
+    }
+}
+
+/// The open interval of times, as fractions of a move by `delta`, during
+/// which the span `min..max` overlaps `other_min..other_max` on one axis.
+fn overlap_times(min: f32, max: f32, delta: f32, other_min: f32, other_max: f32) -> (f32, f32) {
+    if delta == 0.0 {
+        return if min < other_max && other_min < max {
+            (f32::NEG_INFINITY, f32::INFINITY)
+        } else {
+            (f32::INFINITY, f32::NEG_INFINITY)
+        };
+    }
+
+    let touch = (other_min - max) / delta;
+    let leave = (other_max - min) / delta;
+    (touch.min(leave), touch.max(leave))
+}
@@ -1,3 +1,9 @@
+mod hitbox;
 mod obstacle;
+mod obstacle_kind;
+mod pickup;
 
+pub use hitbox::Hitbox;
 pub use obstacle::{Obstacle, ObstacleGap};
+pub use obstacle_kind::{ObstacleKind, VariantWeights};
+pub use pickup::{Pickup, PickupKind};
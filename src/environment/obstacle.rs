@@ -1,11 +1,13 @@
-use crate::config::{
-    OBSTACLE_BASE_GAP_SIZE, OBSTACLE_GAP_MAX_Y, OBSTACLE_GAP_MIN_Y, OBSTACLE_MIN_GAP_SIZE,
-    SCREEN_HEIGHT, SCREEN_WIDTH,
-};
+use crate::config::ObstacleConfig;
+use crate::environment::{Hitbox, ObstacleKind};
+use crate::game::DifficultyParams;
 use crate::player::Player;
+use crate::render::Renderer;
 use bracket_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ObstacleGap {
     center_y: i32,
     half_size: i32,
@@ -19,6 +21,14 @@ impl ObstacleGap {
         }
     }
 
+    pub fn center_y(&self) -> i32 {
+        self.center_y
+    }
+
+    pub fn half_size(&self) -> i32 {
+        self.half_size
+    }
+
     pub fn top_boundary(&self) -> i32 {
         self.center_y - self.half_size
     }
@@ -27,26 +37,119 @@ impl ObstacleGap {
         self.center_y + self.half_size
     }
 
+    /// Returns `true` if row `y` is open; the bottom boundary row is pillar.
     pub fn contains_point(&self, y: i32) -> bool {
-        y >= self.top_boundary() && y <= self.bottom_boundary()
+        y >= self.top_boundary() && y < self.bottom_boundary()
     }
 }
 
-#[derive(Debug, Clone)]
+/// A column of pillars the player must fly through.
+///
+/// The shape depends on the obstacle's [`ObstacleKind`]; moving kinds
+/// change shape as [`Obstacle::update`] is called once per tick.
+///
+/// Obstacles serialize with their gap as it was spawned, under `base_gap`,
+/// together with their age and motion settings, from which moving kinds
+/// derive their shape.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Obstacle {
     x: i32,
+    width: i32,
+    kind: ObstacleKind,
+    #[serde(rename = "base_gap")]
     gap: ObstacleGap,
+    second_gap: Option<ObstacleGap>,
+    min_half_size: i32,
+    amplitude: i32,
+    period: u32,
+    closing_interval: u32,
+    age: u32,
 }
 
 impl Obstacle {
-    pub fn new(x: i32, score: i32) -> Self {
-        let mut rng = RandomNumberGenerator::new();
-        let gap_center = rng.range(OBSTACLE_GAP_MIN_Y, OBSTACLE_GAP_MAX_Y);
-        let gap_half_size = i32::max(OBSTACLE_MIN_GAP_SIZE, OBSTACLE_BASE_GAP_SIZE - score) / 2;
+    /// Creates an obstacle whose kind is picked by the difficulty's
+    /// variant weights.
+    pub fn new(
+        x: i32,
+        difficulty: &DifficultyParams,
+        rng: &mut RandomNumberGenerator,
+        config: &ObstacleConfig,
+    ) -> Self {
+        let kind = ObstacleKind::choose(&difficulty.variant_weights, rng);
+        Self::with_kind(x, kind, difficulty, rng, config)
+    }
+
+    /// Creates an obstacle of a specific kind.
+    ///
+    /// Static obstacles take their gap size from `difficulty`; the other
+    /// kinds keep a wider gap and rely on their own behaviour for
+    /// difficulty.
+    pub fn with_kind(
+        x: i32,
+        kind: ObstacleKind,
+        difficulty: &DifficultyParams,
+        rng: &mut RandomNumberGenerator,
+        config: &ObstacleConfig,
+    ) -> Self {
+        let gap_min_y = difficulty.gap_min_y;
+        let gap_max_y = difficulty.gap_max_y.max(gap_min_y + 1);
+        // An oscillating gap starts far enough from the edges of the range
+        // that its center never drifts out of it.
+        let amplitude = match kind {
+            ObstacleKind::Oscillating => i32::min(
+                config.oscillation_amplitude,
+                (gap_max_y - 1 - gap_min_y) / 2,
+            ),
+            _ => 0,
+        };
+        let gap_center = rng.range(gap_min_y + amplitude, gap_max_y - amplitude);
+        let min_half_size = config.min_gap_size / 2;
+        let gap_half_size = match kind {
+            ObstacleKind::Static => i32::max(config.min_gap_size, difficulty.gap_size) / 2,
+            ObstacleKind::Closing => config.base_gap_size / 2,
+            ObstacleKind::DoubleGap => i32::max(min_half_size, config.variant_gap_size / 2 - 1),
+            _ => i32::max(config.min_gap_size, config.variant_gap_size) / 2,
+        };
+
+        let gap = ObstacleGap::new(gap_center, gap_half_size);
+        let second_gap = (kind == ObstacleKind::DoubleGap).then(|| {
+            let separation = 2 * gap_half_size + config.double_gap_separation;
+            let below = gap_center + separation;
+            let center = if below <= gap_max_y {
+                below
+            } else {
+                gap_center - separation
+            };
+            ObstacleGap::new(center, gap_half_size)
+        });
+
+        Self {
+            x,
+            width: config.width,
+            kind,
+            gap,
+            second_gap,
+            min_half_size,
+            amplitude,
+            period: config.oscillation_period,
+            closing_interval: config.closing_interval,
+            age: 0,
+        }
+    }
 
+    /// Creates a static obstacle with an exact gap.
+    pub fn with_gap(x: i32, gap: ObstacleGap, config: &ObstacleConfig) -> Self {
         Self {
             x,
-            gap: ObstacleGap::new(gap_center, gap_half_size),
+            width: config.width,
+            kind: ObstacleKind::Static,
+            gap,
+            second_gap: None,
+            min_half_size: config.min_gap_size / 2,
+            amplitude: 0,
+            period: config.oscillation_period,
+            closing_interval: config.closing_interval,
+            age: 0,
         }
     }
 
@@ -54,39 +157,174 @@ impl Obstacle {
         self.x
     }
 
-    pub fn has_collision_with(&self, player: &Player) -> bool {
-        if player.x() != self.x {
-            return false;
-        }
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// First world column to the right of the obstacle.
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
 
-        let player_y = player.y() as i32;
-        !self.gap.contains_point(player_y)
+    pub fn kind(&self) -> ObstacleKind {
+        self.kind
     }
 
-    pub fn render(&self, ctx: &mut BTerm, player_x: i32) {
-        let screen_x = self.x - player_x;
+    /// Ticks the obstacle has existed for.
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    /// Advances moving obstacles by one tick.
+    pub fn update(&mut self) {
+        self.age += 1;
+    }
+
+    /// Moves the obstacle to the shape it has after `age` ticks.
+    pub(crate) fn set_age(&mut self, age: u32) {
+        self.age = age;
+    }
 
-        if !(0..SCREEN_WIDTH).contains(&screen_x) {
-            return;
+    /// The obstacle's main gap as it currently stands.
+    ///
+    /// For pillar kinds this is the gap whose edge the pillar ends at.
+    pub fn gap(&self) -> ObstacleGap {
+        match self.kind {
+            ObstacleKind::Oscillating => {
+                let offset = triangle_wave(self.age, self.period, self.amplitude);
+                ObstacleGap::new(self.gap.center_y() + offset, self.gap.half_size())
+            }
+            ObstacleKind::Closing => {
+                let closed = (self.age / self.closing_interval.max(1)) as i32;
+                let half_size = i32::max(self.min_half_size, self.gap.half_size() - closed);
+                ObstacleGap::new(self.gap.center_y(), half_size)
+            }
+            _ => self.gap,
         }
+    }
+
+    /// Rows of the main gap that stay open however the obstacle moves.
+    ///
+    /// Oscillating gaps keep the rows they cover at both ends of their
+    /// swing and closing gaps the rows left once they are fully closed.
+    /// The range is empty if no row is open for the obstacle's whole life.
+    pub fn steady_gap_rows(&self) -> Range<i32> {
+        let gap = self.gap;
+        match self.kind {
+            ObstacleKind::Oscillating => {
+                gap.top_boundary() + self.amplitude..gap.bottom_boundary() - self.amplitude
+            }
+            ObstacleKind::Closing => {
+                let half_size = i32::min(self.min_half_size, gap.half_size());
+                gap.center_y() - half_size..gap.center_y() + half_size
+            }
+            _ => gap.top_boundary()..gap.bottom_boundary(),
+        }
+    }
 
-        self.render_ground(ctx);
-        self.render_obstacle_pillars(ctx, screen_x);
+    /// The second gap of a double-gap obstacle.
+    pub fn second_gap(&self) -> Option<ObstacleGap> {
+        self.second_gap
     }
 
-    fn render_ground(&self, ctx: &mut BTerm) {
-        for x in 0..SCREEN_WIDTH {
-            ctx.set(x, SCREEN_HEIGHT - 1, WHITE, WHITE, to_cp437('#'));
+    /// Hitboxes of every pillar segment of the obstacle.
+    ///
+    /// Segments touching the ceiling or floor extend beyond the screen, so
+    /// only the gaps between them are open.
+    pub fn pillar_hitboxes(&self) -> Vec<Hitbox> {
+        let left = self.x as f32;
+        let right = self.right() as f32;
+        let segment = |top: f32, bottom: f32| Hitbox::from_edges(left, top, right, bottom);
+
+        let gap = self.gap();
+        let ceiling = segment(f32::NEG_INFINITY, gap.top_boundary() as f32);
+        let floor = segment(gap.bottom_boundary() as f32, f32::INFINITY);
+
+        match (self.kind, self.second_gap) {
+            (ObstacleKind::FloorPillar, _) => vec![floor],
+            (ObstacleKind::CeilingPillar, _) => vec![ceiling],
+            (ObstacleKind::DoubleGap, Some(second)) => {
+                let (upper, lower) = if second.center_y() < gap.center_y() {
+                    (second, gap)
+                } else {
+                    (gap, second)
+                };
+
+                vec![
+                    segment(f32::NEG_INFINITY, upper.top_boundary() as f32),
+                    segment(upper.bottom_boundary() as f32, lower.top_boundary() as f32),
+                    segment(lower.bottom_boundary() as f32, f32::INFINITY),
+                ]
+            }
+            _ => vec![ceiling, floor],
+        }
+    }
+
+    /// Returns `true` if the player's hitbox touched a pillar anywhere
+    /// along its last physics update, so a fast player cannot skip over a
+    /// thin obstacle.
+    pub fn has_collision_with(&self, player: &Player) -> bool {
+        let swept = player.swept_hitbox();
+        if swept.right() <= self.x as f32 || self.right() as f32 <= swept.left() {
+            return false;
         }
+
+        let start = player.previous_hitbox();
+        let end = player.hitbox();
+        let (dx, dy) = (end.left() - start.left(), end.top() - start.top());
+        self.pillar_hitboxes()
+            .iter()
+            .any(|pillar| start.overlaps_along(dx, dy, pillar))
     }
 
-    fn render_obstacle_pillars(&self, ctx: &mut BTerm, screen_x: i32) {
-        for y in 0..self.gap.top_boundary() {
-            ctx.set(screen_x, y, RED, BLACK, 179);
+    pub fn render(&self, ctx: &mut dyn Renderer, camera_x: i32) {
+        let (screen_width, screen_height) = ctx.get_char_size();
+        let (screen_width, screen_height) = (screen_width as i32, screen_height as i32);
+        let pillars = self.pillar_hitboxes();
+
+        for world_x in self.x..self.right() {
+            let screen_x = world_x - camera_x;
+
+            if (0..screen_width).contains(&screen_x) {
+                self.render_obstacle_pillars(ctx, screen_x, screen_height, &pillars);
+            }
         }
+    }
+
+    fn render_obstacle_pillars(
+        &self,
+        ctx: &mut dyn Renderer,
+        screen_x: i32,
+        screen_height: i32,
+        pillars: &[Hitbox],
+    ) {
+        let color = self.kind.color();
 
-        for y in self.gap.bottom_boundary()..SCREEN_HEIGHT - 1 {
-            ctx.set(screen_x, y, RED, BLACK, 179);
+        for y in 0..screen_height - 1 {
+            let row = y as f32;
+            let blocked = pillars
+                .iter()
+                .any(|pillar| pillar.top() <= row && row < pillar.bottom());
+
+            if blocked {
+                ctx.set(screen_x, y, color.into(), BLACK.into(), 179);
+            }
         }
     }
 }
+
+/// Integer triangle wave starting at zero and swinging between
+/// `-amplitude` and `amplitude` once every `period` ticks.
+fn triangle_wave(age: u32, period: u32, amplitude: i32) -> i32 {
+    let period = i64::from(period.max(1));
+    let amplitude = i64::from(amplitude);
+    let phase = (i64::from(age) + period / 4) % period;
+
+    let value = if phase < period / 2 {
+        -amplitude + 4 * amplitude * phase / period
+    } else {
+        3 * amplitude - 4 * amplitude * phase / period
+    };
+
+    value as i32
+}
@@ -0,0 +1,148 @@
+use bracket_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Shape and behaviour of an obstacle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ObstacleKind {
+    /// A column with one fixed gap.
+    Static,
+    /// A column whose gap drifts up and down over time.
+    Oscillating,
+    /// A column whose gap slowly narrows.
+    Closing,
+    /// A column with two separate gaps.
+    DoubleGap,
+    /// A single pillar rising from the floor; the space above it is open.
+    FloorPillar,
+    /// A single pillar hanging from the ceiling; the space below it is open.
+    CeilingPillar,
+}
+
+impl ObstacleKind {
+    /// Every obstacle kind.
+    pub const ALL: [ObstacleKind; 6] = [
+        ObstacleKind::Static,
+        ObstacleKind::Oscillating,
+        ObstacleKind::Closing,
+        ObstacleKind::DoubleGap,
+        ObstacleKind::FloorPillar,
+        ObstacleKind::CeilingPillar,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObstacleKind::Static => "static",
+            ObstacleKind::Oscillating => "oscillating",
+            ObstacleKind::Closing => "closing",
+            ObstacleKind::DoubleGap => "double-gap",
+            ObstacleKind::FloorPillar => "floor-pillar",
+            ObstacleKind::CeilingPillar => "ceiling-pillar",
+        }
+    }
+
+    /// Picks a kind at random in proportion to `weights`.
+    ///
+    /// Falls back to [`ObstacleKind::Static`] if every weight is zero.
+    pub fn choose(weights: &VariantWeights, rng: &mut RandomNumberGenerator) -> Self {
+        let total = weights.total();
+        if total == 0 {
+            return ObstacleKind::Static;
+        }
+
+        let mut roll = rng.range(0, total);
+        for kind in Self::ALL {
+            let weight = weights.get(kind);
+            if roll < weight {
+                return kind;
+            }
+            roll -= weight;
+        }
+
+        ObstacleKind::Static
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            ObstacleKind::Static => 0,
+            ObstacleKind::Oscillating => 1,
+            ObstacleKind::Closing => 2,
+            ObstacleKind::DoubleGap => 3,
+            ObstacleKind::FloorPillar => 4,
+            ObstacleKind::CeilingPillar => 5,
+        }
+    }
+
+    /// Colour the kind's pillars are drawn in.
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            ObstacleKind::Static => RED,
+            ObstacleKind::Oscillating => ORANGE,
+            ObstacleKind::Closing => MAGENTA,
+            ObstacleKind::DoubleGap => CRIMSON,
+            ObstacleKind::FloorPillar | ObstacleKind::CeilingPillar => CHOCOLATE,
+        }
+    }
+}
+
+impl fmt::Display for ObstacleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Relative chance of each obstacle kind being spawned.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::environment::{ObstacleKind, VariantWeights};
+///
+/// let weights = VariantWeights::from_pairs(&[
+///     (ObstacleKind::Static, 3),
+///     (ObstacleKind::Closing, 1),
+/// ]);
+///
+/// assert_eq!(weights.get(ObstacleKind::Static), 3);
+/// assert_eq!(weights.get(ObstacleKind::DoubleGap), 0);
+/// assert_eq!(weights.total(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VariantWeights {
+    weights: [u32; ObstacleKind::ALL.len()],
+}
+
+impl VariantWeights {
+    /// Weights under which no kind is ever picked.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Weights under which only `kind` is picked.
+    pub fn only(kind: ObstacleKind) -> Self {
+        Self::from_pairs(&[(kind, 1)])
+    }
+
+    /// Weights for the listed kinds; unlisted kinds get zero.
+    pub fn from_pairs(pairs: &[(ObstacleKind, u32)]) -> Self {
+        let mut weights = Self::none();
+        for &(kind, weight) in pairs {
+            weights.set(kind, weight);
+        }
+        weights
+    }
+
+    pub fn get(&self, kind: ObstacleKind) -> u32 {
+        self.weights[kind.index()]
+    }
+
+    pub fn set(&mut self, kind: ObstacleKind, weight: u32) {
+        self.weights[kind.index()] = weight;
+    }
+
+    /// Sum of all weights.
+    pub fn total(&self) -> u32 {
+        self.weights.iter().sum()
+    }
+}
@@ -0,0 +1,152 @@
+use crate::environment::{Hitbox, Obstacle};
+use crate::render::Renderer;
+use bracket_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Effect a pickup grants the player who collects it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PickupKind {
+    /// Absorbs the next collision with an obstacle.
+    Shield,
+    /// Stretches the time between ticks.
+    SlowMotion,
+    /// Shrinks the player's hitbox.
+    Shrink,
+    /// Multiplies the points each obstacle is worth.
+    Multiplier,
+}
+
+impl PickupKind {
+    /// Every pickup kind.
+    pub const ALL: [PickupKind; 4] = [
+        PickupKind::Shield,
+        PickupKind::SlowMotion,
+        PickupKind::Shrink,
+        PickupKind::Multiplier,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PickupKind::Shield => "shield",
+            PickupKind::SlowMotion => "slow-motion",
+            PickupKind::Shrink => "shrink",
+            PickupKind::Multiplier => "multiplier",
+        }
+    }
+
+    /// Picks a kind at random, each as likely as the others.
+    pub fn choose(rng: &mut RandomNumberGenerator) -> Self {
+        Self::ALL[rng.range(0, Self::ALL.len())]
+    }
+
+    /// Position of the kind in [`PickupKind::ALL`].
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            PickupKind::Shield => 0,
+            PickupKind::SlowMotion => 1,
+            PickupKind::Shrink => 2,
+            PickupKind::Multiplier => 3,
+        }
+    }
+
+    /// Glyph the pickup and its HUD icon are drawn as.
+    pub fn glyph(&self) -> char {
+        match self {
+            PickupKind::Shield => '○',
+            PickupKind::SlowMotion => '≈',
+            PickupKind::Shrink => '▼',
+            PickupKind::Multiplier => '$',
+        }
+    }
+
+    /// Colour the pickup and its HUD icon are drawn in.
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            PickupKind::Shield => CYAN,
+            PickupKind::SlowMotion => LIGHT_BLUE,
+            PickupKind::Shrink => GREEN,
+            PickupKind::Multiplier => GOLD,
+        }
+    }
+}
+
+impl fmt::Display for PickupKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A power-up waiting on the course to be flown through.
+///
+/// Pickups fill a single cell and stay where they were placed, usually in
+/// the middle of the part of an obstacle's gap that never closes.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::environment::{Hitbox, Pickup, PickupKind};
+///
+/// let pickup = Pickup::new(30, 12, PickupKind::Shield);
+///
+/// assert!(pickup.hitbox().overlaps(&Hitbox::new(29.5, 11.0, 2.0, 2.0)));
+/// assert!(!pickup.hitbox().overlaps(&Hitbox::new(31.0, 11.0, 2.0, 2.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pickup {
+    x: i32,
+    y: i32,
+    kind: PickupKind,
+}
+
+impl Pickup {
+    pub fn new(x: i32, y: i32, kind: PickupKind) -> Self {
+        Self { x, y, kind }
+    }
+
+    /// Creates a pickup in the middle of the rows of the main gap of
+    /// `obstacle` that stay open while it moves, or `None` if the gap
+    /// leaves no such row.
+    pub fn in_gap_of(obstacle: &Obstacle, kind: PickupKind) -> Option<Self> {
+        let rows = obstacle.steady_gap_rows();
+        (!rows.is_empty()).then(|| Self::new(obstacle.x(), (rows.start + rows.end) / 2, kind))
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    pub fn kind(&self) -> PickupKind {
+        self.kind
+    }
+
+    /// First world column to the right of the pickup.
+    pub fn right(&self) -> i32 {
+        self.x + 1
+    }
+
+    /// The cell the pickup fills.
+    pub fn hitbox(&self) -> Hitbox {
+        Hitbox::new(self.x as f32, self.y as f32, 1.0, 1.0)
+    }
+
+    pub fn render(&self, ctx: &mut dyn Renderer, camera_x: i32) {
+        let (screen_width, _) = ctx.get_char_size();
+        let screen_x = self.x - camera_x;
+
+        if (0..screen_width as i32).contains(&screen_x) {
+            ctx.set(
+                screen_x,
+                self.y,
+                self.kind.color().into(),
+                NAVY.into(),
+                to_cp437(self.kind.glyph()),
+            );
+        }
+    }
+}
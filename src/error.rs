@@ -25,6 +25,22 @@ pub enum GameError {
         height: i32,
     },
 
+    /// The time between physics updates is not a usable duration.
+    #[error("Invalid frame duration: {frame_duration_ms} ms")]
+    InvalidFrameDuration {
+        /// Configured duration between updates in milliseconds.
+        frame_duration_ms: f32,
+    },
+
+    /// Sprite or console tiles would have no size.
+    #[error("Invalid tile sizes: tile_size={tile_size}, console_tile_size={console_tile_size}")]
+    InvalidTileSize {
+        /// Configured sprite tile size.
+        tile_size: i32,
+        /// Configured console tile size.
+        console_tile_size: i32,
+    },
+
     /// Player position is outside valid game boundaries.
     #[error("Player position out of bounds: x={x}, y={y}")]
     PlayerOutOfBounds {
@@ -34,12 +50,173 @@ pub enum GameError {
         y: f32,
     },
 
+    /// Obstacle gap centers cannot be placed on the screen.
+    #[error(
+        "Invalid obstacle gap range: gap_min_y={min_y}, gap_max_y={max_y}, screen height={screen_height}"
+    )]
+    InvalidObstacleGapRange {
+        /// Configured minimum gap center.
+        min_y: i32,
+        /// Configured maximum gap center.
+        max_y: i32,
+        /// Screen height the range must fit within.
+        screen_height: i32,
+    },
+
+    /// Obstacle gap sizes are inconsistent.
+    #[error(
+        "Invalid obstacle gap sizes: min_gap_size={min_gap_size}, base_gap_size={base_gap_size}"
+    )]
+    InvalidObstacleGapSize {
+        /// Configured minimum gap size.
+        min_gap_size: i32,
+        /// Configured base gap size.
+        base_gap_size: i32,
+    },
+
+    /// Obstacles would have no width or overlap each other horizontally.
+    #[error("Invalid obstacle spacing: spacing={spacing}, width={width}")]
+    InvalidObstacleSpacing {
+        /// Configured horizontal distance between obstacles.
+        spacing: i32,
+        /// Configured obstacle width.
+        width: i32,
+    },
+
+    /// Moving or double-gap obstacles are configured so they cannot work.
+    #[error("Invalid obstacle variants: {reason}")]
+    InvalidObstacleVariants {
+        /// Description of the offending setting.
+        reason: String,
+    },
+
+    /// Gravity, the velocity limit or the hitbox size is not a finite,
+    /// positive number.
+    #[error(
+        "Invalid physics: gravity={gravity}, max_velocity={max_velocity}, hitbox_size={hitbox_size}"
+    )]
+    InvalidPhysics {
+        /// Configured gravitational acceleration.
+        gravity: f32,
+        /// Configured maximum fall speed.
+        max_velocity: f32,
+        /// Configured width and height of the player's hitbox.
+        hitbox_size: f32,
+    },
+
+    /// The player would not move forward every tick.
+    #[error("Movement speed {movement_speed} must be at least one column per tick")]
+    InvalidMovementSpeed {
+        /// Configured columns the player advances per tick.
+        movement_speed: i32,
+    },
+
+    /// A flap is too weak to lift the player against gravity.
+    #[error("Flap strength {flap_strength} cannot overcome gravity {gravity}")]
+    FlapTooWeak {
+        /// Velocity applied by a flap after clamping.
+        flap_strength: f32,
+        /// Gravity applied on the same tick.
+        gravity: f32,
+    },
+
+    /// The high-score file exists but its contents could not be understood.
+    #[error("High score file {path} is corrupted: {reason}")]
+    CorruptHighScores {
+        /// Path to the corrupted high-score file.
+        path: String,
+        /// Description of what is wrong with the file.
+        reason: String,
+    },
+
+    /// Playing a replay back did not reproduce the recorded result.
+    #[error("Replay {path} diverged: recorded score {recorded}, replayed score {replayed}")]
+    ReplayMismatch {
+        /// Path to the replay file.
+        path: String,
+        /// Score stored in the replay.
+        recorded: i32,
+        /// Score reached when playing the replay back.
+        replayed: i32,
+    },
+
     /// Game asset file could not be loaded.
     #[error("Asset loading failed: {path}")]
     AssetLoadingFailed {
         /// Path to the asset that failed to load.
         path: String,
     },
+
+    /// A game file could not be read or written.
+    #[error("I/O error on {path}: {source}")]
+    Io {
+        /// Path to the file that failed.
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A configuration file exists but its contents could not be understood.
+    #[error("Invalid configuration {path}: {reason}")]
+    InvalidConfig {
+        /// Path to the malformed configuration file.
+        path: String,
+        /// Description of what is wrong with the file.
+        reason: String,
+    },
+
+    /// A replay file exists but its contents could not be understood.
+    #[error("Invalid replay file {path}: {reason}")]
+    InvalidReplay {
+        /// Path to the malformed replay file.
+        path: String,
+        /// Description of what is wrong with the file.
+        reason: String,
+    },
+
+    /// A character registry file exists but its contents could not be understood.
+    #[error("Invalid character file {path}: {reason}")]
+    InvalidSkinFile {
+        /// Path to the malformed registry file.
+        path: String,
+        /// Description of what is wrong with the file.
+        reason: String,
+    },
+
+    /// A character's animation cannot be drawn from the sprite sheet.
+    #[error("Invalid character {name}: {reason}")]
+    InvalidSkin {
+        /// Name of the character.
+        name: String,
+        /// Description of what is wrong with it.
+        reason: String,
+    },
+
+    /// Power-up settings would make a pickup useless or break the game.
+    #[error("Invalid power-up settings: {reason}")]
+    InvalidPowerUps {
+        /// Description of what is wrong with the settings.
+        reason: String,
+    },
+
+    /// A network connection could not be set up or broke down.
+    #[error("Network error with {peer}: {reason}")]
+    Network {
+        /// Address of the other side of the connection.
+        peer: String,
+        /// Description of what went wrong.
+        reason: String,
+    },
+
+    /// A genome file exists but its contents could not be understood.
+    #[error("Invalid genome file {path}: {reason}")]
+    InvalidGenome {
+        /// Path to the malformed genome file.
+        path: String,
+        /// Description of what is wrong with the file.
+        reason: String,
+    },
 }
 
 /// Convenient Result type alias for game operations.
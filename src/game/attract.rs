@@ -0,0 +1,53 @@
+use crate::agent::{Agent, LookaheadAgent, Observation};
+use crate::config::GameConfig;
+use crate::game::{GameTimer, Simulation, random_seed};
+
+/// A demo run played by the lookahead bot behind the main menu.
+///
+/// A new course is started whenever the bot crashes.
+#[derive(Debug)]
+pub struct AttractMode {
+    config: GameConfig,
+    simulation: Simulation,
+    agent: LookaheadAgent,
+    timer: GameTimer,
+}
+
+impl AttractMode {
+    pub fn new(config: &GameConfig) -> Self {
+        Self {
+            config: config.clone(),
+            simulation: Simulation::with_config(config.clone(), random_seed()),
+            agent: LookaheadAgent::new(),
+            timer: GameTimer::new(config.display.frame_duration_ms),
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    pub fn agent(&self) -> &LookaheadAgent {
+        &self.agent
+    }
+
+    /// Advances the demo by the time elapsed since the last frame.
+    pub fn update(&mut self, delta_time: f32) {
+        let time_scale = self.simulation.player().time_scale();
+        self.timer
+            .set_frame_duration(self.config.display.frame_duration_ms * time_scale);
+        self.timer.update(delta_time);
+        if !self.timer.should_tick() {
+            return;
+        }
+
+        if self.simulation.is_game_over() {
+            self.simulation = Simulation::with_config(self.config.clone(), random_seed());
+            self.agent.reset();
+            return;
+        }
+
+        let input = self.agent.act(&Observation::of(&self.simulation));
+        self.simulation.step(input);
+    }
+}
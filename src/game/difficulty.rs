@@ -0,0 +1,384 @@
+//! Difficulty progression.
+//!
+//! A [`DifficultyCurve`] decides how hard the course is at any point of a
+//! run: how large gaps are, where they may sit, how far apart obstacles
+//! are, how fast the player scrolls and which obstacle kinds appear.
+//! Curves interpolate between the easiest and hardest settings of a
+//! [`DifficultyBounds`]; the shape of the curve decides how quickly.
+
+use crate::config::{
+    DIFFICULTY_HARD_TICKS_PER_LEVEL, DIFFICULTY_LOGISTIC_STEEPNESS, DIFFICULTY_STEPPED_STEPS,
+    GameConfig,
+};
+use crate::environment::{ObstacleKind, VariantWeights};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Course settings in effect at one point of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DifficultyParams {
+    /// Size of the gap in static obstacles.
+    pub gap_size: i32,
+    /// Lowest row a gap center may be placed at.
+    pub gap_min_y: i32,
+    /// Row gap centers must be placed above.
+    pub gap_max_y: i32,
+    /// Horizontal distance to the next obstacle.
+    pub spacing: i32,
+    /// Columns the player advances per tick.
+    pub scroll_speed: i32,
+    /// Relative chance of spawning each obstacle kind.
+    pub variant_weights: VariantWeights,
+}
+
+impl DifficultyParams {
+    /// Interpolates between two settings; `progress` is clamped to `0.0..=1.0`.
+    pub fn lerp(&self, other: &Self, progress: f32) -> Self {
+        let progress = progress.clamp(0.0, 1.0);
+        let mix = |from: i32, to: i32| from + ((to - from) as f32 * progress).round() as i32;
+
+        let mut variant_weights = VariantWeights::none();
+        for kind in ObstacleKind::ALL {
+            let weight = mix(
+                self.variant_weights.get(kind) as i32,
+                other.variant_weights.get(kind) as i32,
+            );
+            variant_weights.set(kind, weight.max(0) as u32);
+        }
+
+        Self {
+            gap_size: mix(self.gap_size, other.gap_size),
+            gap_min_y: mix(self.gap_min_y, other.gap_min_y),
+            gap_max_y: mix(self.gap_max_y, other.gap_max_y),
+            spacing: mix(self.spacing, other.spacing),
+            scroll_speed: mix(self.scroll_speed, other.scroll_speed),
+            variant_weights,
+        }
+    }
+}
+
+/// The easiest and hardest settings of a run, and how long it takes to go
+/// from one to the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DifficultyBounds {
+    /// Settings at the start of a run.
+    pub start: DifficultyParams,
+    /// Settings once the ramp is complete.
+    pub end: DifficultyParams,
+    /// Difficulty level at which the end settings are reached.
+    pub ramp_length: i32,
+    /// Ticks that count as one level of difficulty, if time should matter.
+    pub ticks_per_level: Option<u64>,
+}
+
+impl DifficultyBounds {
+    /// Difficulty level reached at `score` after `elapsed_ticks`.
+    pub fn level(&self, score: i32, elapsed_ticks: u64) -> f32 {
+        let time_levels = self
+            .ticks_per_level
+            .filter(|&ticks| ticks > 0)
+            .map_or(0.0, |ticks| elapsed_ticks as f32 / ticks as f32);
+
+        score as f32 + time_levels
+    }
+
+    /// Settings at `progress` along the ramp.
+    pub fn at(&self, progress: f32) -> DifficultyParams {
+        self.start.lerp(&self.end, progress)
+    }
+}
+
+/// Maps the state of a run to the course settings in effect.
+///
+/// Implementations must be deterministic so runs can be replayed.
+pub trait DifficultyCurve: fmt::Debug + Send + Sync {
+    /// Settings for a run at `score` after `elapsed_ticks` ticks.
+    fn params(&self, score: i32, elapsed_ticks: u64) -> DifficultyParams;
+}
+
+/// Ramps difficulty up evenly with every point.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{DifficultyCurve, DifficultyPreset, LinearCurve};
+///
+/// let config = GameConfig::default();
+/// let curve = LinearCurve::new(DifficultyPreset::Normal.bounds(&config));
+///
+/// assert_eq!(curve.params(0, 0).gap_size, config.obstacle.base_gap_size);
+/// assert_eq!(curve.params(100, 0).gap_size, config.obstacle.min_gap_size);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearCurve {
+    bounds: DifficultyBounds,
+}
+
+impl LinearCurve {
+    pub fn new(bounds: DifficultyBounds) -> Self {
+        Self { bounds }
+    }
+}
+
+impl DifficultyCurve for LinearCurve {
+    fn params(&self, score: i32, elapsed_ticks: u64) -> DifficultyParams {
+        let level = self.bounds.level(score, elapsed_ticks);
+        self.bounds
+            .at(level / self.bounds.ramp_length.max(1) as f32)
+    }
+}
+
+/// Holds difficulty on plateaus and jumps up at regular score intervals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteppedCurve {
+    bounds: DifficultyBounds,
+    steps: i32,
+}
+
+impl SteppedCurve {
+    pub fn new(bounds: DifficultyBounds) -> Self {
+        Self::with_steps(bounds, DIFFICULTY_STEPPED_STEPS)
+    }
+
+    /// Creates a curve that reaches its end settings in `steps` jumps.
+    pub fn with_steps(bounds: DifficultyBounds, steps: i32) -> Self {
+        Self {
+            bounds,
+            steps: steps.max(1),
+        }
+    }
+}
+
+impl DifficultyCurve for SteppedCurve {
+    fn params(&self, score: i32, elapsed_ticks: u64) -> DifficultyParams {
+        let level = self.bounds.level(score, elapsed_ticks);
+        let step_length = self.bounds.ramp_length.max(1) as f32 / self.steps as f32;
+        let step = (level / step_length).floor();
+
+        self.bounds.at(step / self.steps as f32)
+    }
+}
+
+/// Starts gently, ramps quickly through the middle and eases into the
+/// hardest settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogisticCurve {
+    bounds: DifficultyBounds,
+}
+
+impl LogisticCurve {
+    pub fn new(bounds: DifficultyBounds) -> Self {
+        Self { bounds }
+    }
+}
+
+impl DifficultyCurve for LogisticCurve {
+    fn params(&self, score: i32, elapsed_ticks: u64) -> DifficultyParams {
+        let ramp = self.bounds.ramp_length.max(1) as f32;
+        let level = self.bounds.level(score, elapsed_ticks);
+        let logistic = |x: f32| 1.0 / (1.0 + (-DIFFICULTY_LOGISTIC_STEEPNESS * (x - 0.5)).exp());
+
+        let low = logistic(0.0);
+        let high = logistic(1.0);
+        let progress = (logistic(level / ramp) - low) / (high - low);
+
+        self.bounds.at(progress)
+    }
+}
+
+/// Shape of difficulty progression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CurveShape {
+    /// See [`LinearCurve`].
+    #[default]
+    Linear,
+    /// See [`SteppedCurve`].
+    Stepped,
+    /// See [`LogisticCurve`].
+    Logistic,
+}
+
+impl CurveShape {
+    /// Every curve shape.
+    pub const ALL: [CurveShape; 3] = [
+        CurveShape::Linear,
+        CurveShape::Stepped,
+        CurveShape::Logistic,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CurveShape::Linear => "linear",
+            CurveShape::Stepped => "stepped",
+            CurveShape::Logistic => "logistic",
+        }
+    }
+
+    /// Builds a curve of this shape over `bounds`.
+    pub fn build(&self, bounds: DifficultyBounds) -> Arc<dyn DifficultyCurve> {
+        match self {
+            CurveShape::Linear => Arc::new(LinearCurve::new(bounds)),
+            CurveShape::Stepped => Arc::new(SteppedCurve::new(bounds)),
+            CurveShape::Logistic => Arc::new(LogisticCurve::new(bounds)),
+        }
+    }
+}
+
+impl fmt::Display for CurveShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Named sets of difficulty bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DifficultyPreset {
+    /// Wider gaps, roomier spacing and a slow ramp.
+    Easy,
+    /// The configured obstacle settings, ramping over the base gap size.
+    #[default]
+    Normal,
+    /// Tighter gaps and spacing, a fast ramp that also advances with time,
+    /// and a faster scroll speed once the ramp is complete.
+    Hard,
+}
+
+impl DifficultyPreset {
+    /// Every preset, in menu order.
+    pub const ALL: [DifficultyPreset; 3] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "easy",
+            DifficultyPreset::Normal => "normal",
+            DifficultyPreset::Hard => "hard",
+        }
+    }
+
+    /// The preset after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|preset| preset == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Bounds of this preset, derived from the configured obstacle and
+    /// physics settings.
+    pub fn bounds(&self, config: &GameConfig) -> DifficultyBounds {
+        let obstacle = &config.obstacle;
+        let min_spacing = obstacle.width + 1;
+        let base_ramp = (obstacle.base_gap_size - obstacle.min_gap_size).max(1);
+
+        let normal_start = DifficultyParams {
+            gap_size: obstacle.base_gap_size,
+            gap_min_y: obstacle.gap_min_y,
+            gap_max_y: obstacle.gap_max_y,
+            spacing: obstacle.spacing,
+            scroll_speed: config.physics.movement_speed,
+            variant_weights: VariantWeights::only(ObstacleKind::Static),
+        };
+        let normal_end = DifficultyParams {
+            gap_size: obstacle.min_gap_size,
+            variant_weights: VariantWeights::from_pairs(&[
+                (ObstacleKind::Static, 2),
+                (ObstacleKind::FloorPillar, 1),
+                (ObstacleKind::CeilingPillar, 1),
+                (ObstacleKind::Oscillating, 2),
+                (ObstacleKind::Closing, 2),
+                (ObstacleKind::DoubleGap, 1),
+            ]),
+            ..normal_start
+        };
+
+        match self {
+            DifficultyPreset::Easy => DifficultyBounds {
+                start: DifficultyParams {
+                    gap_size: obstacle.base_gap_size + 4,
+                    spacing: obstacle.spacing + 5,
+                    ..normal_start
+                },
+                end: DifficultyParams {
+                    gap_size: (obstacle.min_gap_size + 4).min(obstacle.base_gap_size),
+                    spacing: obstacle.spacing + 5,
+                    variant_weights: VariantWeights::from_pairs(&[
+                        (ObstacleKind::Static, 3),
+                        (ObstacleKind::FloorPillar, 1),
+                        (ObstacleKind::CeilingPillar, 1),
+                        (ObstacleKind::Oscillating, 1),
+                    ]),
+                    ..normal_start
+                },
+                ramp_length: base_ramp * 2,
+                ticks_per_level: None,
+            },
+            DifficultyPreset::Normal => DifficultyBounds {
+                start: normal_start,
+                end: normal_end,
+                ramp_length: base_ramp,
+                ticks_per_level: None,
+            },
+            DifficultyPreset::Hard => DifficultyBounds {
+                start: DifficultyParams {
+                    gap_size: (obstacle.base_gap_size - 2).max(obstacle.min_gap_size),
+                    spacing: (obstacle.spacing - 5).max(min_spacing),
+                    variant_weights: VariantWeights::from_pairs(&[
+                        (ObstacleKind::Static, 3),
+                        (ObstacleKind::Oscillating, 1),
+                    ]),
+                    ..normal_start
+                },
+                end: DifficultyParams {
+                    gap_size: obstacle.min_gap_size,
+                    spacing: (obstacle.spacing - 5).max(min_spacing),
+                    scroll_speed: config.physics.movement_speed.saturating_add(1),
+                    variant_weights: VariantWeights::from_pairs(&[
+                        (ObstacleKind::Static, 1),
+                        (ObstacleKind::FloorPillar, 1),
+                        (ObstacleKind::CeilingPillar, 1),
+                        (ObstacleKind::Oscillating, 2),
+                        (ObstacleKind::Closing, 2),
+                        (ObstacleKind::DoubleGap, 2),
+                    ]),
+                    ..normal_start
+                },
+                ramp_length: (base_ramp / 2).max(1),
+                ticks_per_level: Some(DIFFICULTY_HARD_TICKS_PER_LEVEL),
+            },
+        }
+    }
+}
+
+impl fmt::Display for DifficultyPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for DifficultyPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(DifficultyPreset::name).collect();
+                format!(
+                    "unknown difficulty '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
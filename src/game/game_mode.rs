@@ -1,12 +1,10 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum GameMode {
+    #[default]
     Menu,
     Playing,
+    Paused,
     GameOver,
-}
-
-impl Default for GameMode {
-    fn default() -> Self {
-        Self::Menu
-    }
+    HighScores,
+    Characters,
 }
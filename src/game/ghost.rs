@@ -0,0 +1,128 @@
+use crate::game::{Replay, ReplayPlayback, Simulation};
+use std::fmt;
+
+/// How far a live run is ahead of a [`Ghost`], or behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GhostLead {
+    /// Obstacles the live run has passed beyond the ghost; negative while
+    /// the ghost has passed more.
+    Obstacles(i32),
+    /// Both have passed the same obstacles: rows the live player is
+    /// nearer than the ghost to the center of the next gap, negative while
+    /// the ghost is nearer.
+    Rows(i32),
+}
+
+impl fmt::Display for GhostLead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: i32, noun: &str| match count.abs() {
+            1 => format!("1 {}", noun),
+            count => format!("{} {}s", count, noun),
+        };
+
+        match *self {
+            GhostLead::Obstacles(0) | GhostLead::Rows(0) => write!(f, "level"),
+            GhostLead::Obstacles(lead) if lead > 0 => {
+                write!(f, "ahead by {}", plural(lead, "obstacle"))
+            }
+            GhostLead::Obstacles(lead) => write!(f, "behind by {}", plural(lead, "obstacle")),
+            GhostLead::Rows(lead) if lead > 0 => {
+                write!(f, "level, {} nearer the gap", plural(lead, "row"))
+            }
+            GhostLead::Rows(lead) => {
+                write!(f, "level, {} further from the gap", plural(lead, "row"))
+            }
+        }
+    }
+}
+
+/// A recorded run played back alongside a live one on the same course.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{Ghost, GhostLead, ReplayRecorder, Simulation, TickInput};
+///
+/// let mut simulation = Simulation::with_seed(9);
+/// let mut recorder = ReplayRecorder::new(9, GameConfig::default());
+/// while !simulation.is_game_over() {
+///     let input = if simulation.tick() % 6 == 0 { TickInput::FLAP } else { TickInput::IDLE };
+///     recorder.record(simulation.tick(), input);
+///     simulation.step(input);
+/// }
+///
+/// let mut ghost = Ghost::new(recorder.finish(&simulation));
+/// let live = Simulation::with_seed(9);
+/// while !ghost.is_finished() {
+///     ghost.step();
+/// }
+///
+/// assert_eq!(ghost.simulation().score(), simulation.score());
+/// assert_eq!(ghost.lead(&live), GhostLead::Obstacles(-simulation.obstacles_passed()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ghost {
+    playback: ReplayPlayback,
+    simulation: Simulation,
+}
+
+impl Ghost {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            simulation: Simulation::with_config(replay.config.clone(), replay.seed),
+            playback: ReplayPlayback::new(replay),
+        }
+    }
+
+    pub fn replay(&self) -> &Replay {
+        self.playback.replay()
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    /// Returns `true` once the recorded run has ended.
+    pub fn is_finished(&self) -> bool {
+        self.simulation.is_game_over() || self.playback.is_finished(self.simulation.tick())
+    }
+
+    /// Plays the next recorded tick, unless the run has ended.
+    pub fn step(&mut self) {
+        if !self.is_finished() {
+            let input = self.playback.next_input(self.simulation.tick());
+            self.simulation.step(input);
+        }
+    }
+
+    /// Starts the recorded run again from its first tick.
+    pub fn restart(&mut self) {
+        *self = Self::new(self.replay().clone());
+    }
+
+    /// How far the live run in `simulation` is ahead of the ghost: by
+    /// obstacles passed, or, while those are equal, by how near each
+    /// player is to the center of the next gap.
+    pub fn lead(&self, simulation: &Simulation) -> GhostLead {
+        let obstacles = simulation.obstacles_passed() - self.simulation.obstacles_passed();
+        if obstacles != 0 {
+            return GhostLead::Obstacles(obstacles);
+        }
+
+        let rows = distance_to_next_gap(&self.simulation) - distance_to_next_gap(simulation);
+        GhostLead::Rows(rows.round() as i32)
+    }
+}
+
+/// Rows between the player of `simulation` and the nearest gap center of
+/// the next obstacle, or zero if there is none.
+fn distance_to_next_gap(simulation: &Simulation) -> f32 {
+    let y = simulation.player().y();
+    simulation.obstacles().front().map_or(0.0, |obstacle| {
+        std::iter::once(obstacle.gap())
+            .chain(obstacle.second_gap())
+            .map(|gap| (y - gap.center_y() as f32).abs())
+            .fold(f32::INFINITY, f32::min)
+    })
+}
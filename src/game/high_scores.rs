@@ -0,0 +1,221 @@
+//! Persistent high-score table.
+//!
+//! The table keeps the best runs across sessions in a TOML file inside the
+//! user's data directory. Entries are ordered best first and the table
+//! never grows beyond its capacity.
+
+use crate::config::{DATA_DIR_NAME, HIGH_SCORE_CAPACITY, HIGH_SCORE_FILE_NAME};
+use crate::error::{GameError, GameResult};
+use crate::game::PlayMode;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single ranked run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    /// Name the player entered.
+    pub name: String,
+    /// Score the run reached.
+    pub score: i32,
+    /// Seed of the run's obstacle course.
+    pub seed: u64,
+    /// Date the run was played, as `YYYY-MM-DD` in UTC.
+    pub date: String,
+    /// Rule set the run was played under.
+    pub mode: PlayMode,
+}
+
+impl HighScoreEntry {
+    /// Creates an entry dated today.
+    pub fn new(name: impl Into<String>, score: i32, seed: u64, mode: PlayMode) -> Self {
+        Self {
+            name: name.into(),
+            score,
+            seed,
+            date: today(),
+            mode,
+        }
+    }
+}
+
+/// The best runs, ordered from highest to lowest score.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::game::{HighScoreEntry, HighScoreTable, PlayMode};
+///
+/// let mut table = HighScoreTable::with_capacity(2);
+/// table.insert(HighScoreEntry::new("Cheems", 3, 1, PlayMode::Endless));
+/// table.insert(HighScoreEntry::new("Doge", 7, 2, PlayMode::Endless));
+///
+/// assert!(!table.qualifies(2));
+/// assert_eq!(table.insert(HighScoreEntry::new("Walter", 5, 3, PlayMode::Endless)), Some(1));
+/// assert_eq!(table.entries()[0].name, "Doge");
+/// assert_eq!(table.entries().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    #[serde(skip, default = "default_capacity")]
+    capacity: usize,
+    #[serde(default)]
+    entries: Vec<HighScoreEntry>,
+}
+
+impl HighScoreTable {
+    pub fn new() -> Self {
+        Self::with_capacity(HIGH_SCORE_CAPACITY)
+    }
+
+    /// Creates an empty table that keeps at most `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Reads the table from a TOML file.
+    ///
+    /// A missing file yields an empty table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file exists but cannot be read and
+    /// `GameError::CorruptHighScores` if its contents are malformed.
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::new()),
+            Err(source) => {
+                return Err(GameError::Io {
+                    path: path.display().to_string(),
+                    source,
+                });
+            }
+        };
+
+        let mut table: Self =
+            toml::from_str(&contents).map_err(|e| GameError::CorruptHighScores {
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            })?;
+
+        table
+            .entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        table.entries.truncate(table.capacity);
+        Ok(table)
+    }
+
+    /// Writes the table to a TOML file, creating parent directories as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file or its directory cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> GameResult<()> {
+        let path = path.as_ref();
+        let io_error = |source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let contents = toml::to_string(self).map_err(|e| GameError::CorruptHighScores {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        fs::write(path, contents).map_err(io_error)
+    }
+
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    /// Returns `true` if a run with `score` would earn a place in the table.
+    pub fn qualifies(&self, score: i32) -> bool {
+        if score <= 0 {
+            return false;
+        }
+
+        self.entries.len() < self.capacity
+            || self
+                .entries
+                .last()
+                .is_some_and(|lowest| score > lowest.score)
+    }
+
+    /// Adds an entry and returns its zero-based rank, or `None` if it did
+    /// not make the table.
+    ///
+    /// Ties are ranked below existing entries with the same score.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+
+        let rank = self
+            .entries
+            .iter()
+            .position(|existing| existing.score < entry.score)
+            .unwrap_or(self.entries.len());
+
+        self.entries.insert(rank, entry);
+        self.entries.truncate(self.capacity);
+        Some(rank)
+    }
+}
+
+impl Default for HighScoreTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_capacity() -> usize {
+    HIGH_SCORE_CAPACITY
+}
+
+/// Default location of the high-score file, inside the user's data directory.
+pub fn default_high_score_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DATA_DIR_NAME).join(HIGH_SCORE_FILE_NAME))
+}
+
+/// Today's date in UTC, formatted as `YYYY-MM-DD`.
+fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
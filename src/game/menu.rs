@@ -1,44 +1,414 @@
-use crate::game::GameState;
+use crate::agent::Agent;
+use crate::game::transitions::{render_game_frame, render_simulation};
+use crate::game::{GameMode, GameState, Seat};
+use crate::net::PeerStatus;
+use crate::render::{Renderer, SPRITE_LAYER, TEXT_LAYER};
 use bracket_lib::prelude::*;
 
+const MENU_DEMO_Y: i32 = 0;
 const MENU_TITLE_Y: i32 = 5;
 const MENU_OPTIONS_START_Y: i32 = 8;
 const MENU_SCORE_Y: i32 = 6;
+const MENU_INFO_START_Y: i32 = 14;
+const HIGH_SCORES_LIST_START_Y: i32 = 8;
+const CHARACTERS_LIST_START_Y: i32 = 8;
+/// Rows between the characters menu's footer and its preview sprite.
+const CHARACTERS_PREVIEW_OFFSET_Y: f32 = 3.0;
+
+const CHARACTER_KEYS: [VirtualKeyCode; 9] = [
+    VirtualKeyCode::Key1,
+    VirtualKeyCode::Key2,
+    VirtualKeyCode::Key3,
+    VirtualKeyCode::Key4,
+    VirtualKeyCode::Key5,
+    VirtualKeyCode::Key6,
+    VirtualKeyCode::Key7,
+    VirtualKeyCode::Key8,
+    VirtualKeyCode::Key9,
+];
+
+const NAME_ENTRY_KEYS: [(VirtualKeyCode, char); 36] = [
+    (VirtualKeyCode::A, 'A'),
+    (VirtualKeyCode::B, 'B'),
+    (VirtualKeyCode::C, 'C'),
+    (VirtualKeyCode::D, 'D'),
+    (VirtualKeyCode::E, 'E'),
+    (VirtualKeyCode::F, 'F'),
+    (VirtualKeyCode::G, 'G'),
+    (VirtualKeyCode::H, 'H'),
+    (VirtualKeyCode::I, 'I'),
+    (VirtualKeyCode::J, 'J'),
+    (VirtualKeyCode::K, 'K'),
+    (VirtualKeyCode::L, 'L'),
+    (VirtualKeyCode::M, 'M'),
+    (VirtualKeyCode::N, 'N'),
+    (VirtualKeyCode::O, 'O'),
+    (VirtualKeyCode::P, 'P'),
+    (VirtualKeyCode::Q, 'Q'),
+    (VirtualKeyCode::R, 'R'),
+    (VirtualKeyCode::S, 'S'),
+    (VirtualKeyCode::T, 'T'),
+    (VirtualKeyCode::U, 'U'),
+    (VirtualKeyCode::V, 'V'),
+    (VirtualKeyCode::W, 'W'),
+    (VirtualKeyCode::X, 'X'),
+    (VirtualKeyCode::Y, 'Y'),
+    (VirtualKeyCode::Z, 'Z'),
+    (VirtualKeyCode::Key0, '0'),
+    (VirtualKeyCode::Key1, '1'),
+    (VirtualKeyCode::Key2, '2'),
+    (VirtualKeyCode::Key3, '3'),
+    (VirtualKeyCode::Key4, '4'),
+    (VirtualKeyCode::Key5, '5'),
+    (VirtualKeyCode::Key6, '6'),
+    (VirtualKeyCode::Key7, '7'),
+    (VirtualKeyCode::Key8, '8'),
+    (VirtualKeyCode::Key9, '9'),
+];
 
 pub fn main_menu(state: &mut GameState, ctx: &mut BTerm) {
-    render_menu_background(ctx);
+    state.update_attract(ctx.frame_time_ms);
+    render_main_menu(state, ctx);
+
+    match ctx.key {
+        Some(VirtualKeyCode::D) => state.cycle_difficulty(),
+        Some(VirtualKeyCode::V) => state.start_versus(),
+        Some(VirtualKeyCode::C) => state.set_mode(GameMode::Characters),
+        // Playing from the main menu is always a single-player run.
+        Some(VirtualKeyCode::P) => state.end_versus(),
+        _ => {}
+    }
+    handle_menu_input(state, ctx);
+}
+
+/// Draws the main menu over the attract demo, if one is running.
+pub fn render_main_menu(state: &GameState, ctx: &mut dyn Renderer) {
+    match state.attract() {
+        Some(attract) => {
+            render_simulation(attract.simulation(), ctx);
+            ctx.print(
+                0,
+                MENU_DEMO_Y,
+                &format!(
+                    "Demo: {} bot  Score: {}",
+                    attract.agent().name(),
+                    attract.simulation().score()
+                ),
+            );
+        }
+        None => render_menu_background(ctx),
+    }
 
     ctx.print_centered(MENU_TITLE_Y, "Welcome to Flappy Cheems.");
     ctx.print_centered(MENU_OPTIONS_START_Y, "▌P▐ Play game");
-    ctx.print_centered(MENU_OPTIONS_START_Y + 1, "▌Q▐ Quit game");
+    ctx.print_centered(MENU_OPTIONS_START_Y + 1, "▌H▐ High scores");
+    ctx.print_centered(
+        MENU_OPTIONS_START_Y + 2,
+        &format!(
+            "▌V▐ Versus ({} vs {})",
+            Seat::One.key_name(),
+            Seat::Two.key_name()
+        ),
+    );
+    ctx.print_centered(MENU_OPTIONS_START_Y + 3, "▌D▐ Change difficulty");
+    ctx.print_centered(MENU_OPTIONS_START_Y + 4, "▌C▐ Characters");
+    ctx.print_centered(MENU_OPTIONS_START_Y + 5, "▌Q▐ Quit game");
 
-    handle_menu_input(state, ctx);
+    let difficulty = state.config().difficulty;
+    ctx.print_centered(
+        MENU_INFO_START_Y,
+        &format!(
+            "Mode: {}  Difficulty: {} ({})",
+            state.config().mode,
+            difficulty.preset,
+            difficulty.curve
+        ),
+    );
 }
 
 pub fn game_over_menu(state: &mut GameState, ctx: &mut BTerm) {
+    state.poll_network();
+    render_game_over_menu(state, ctx);
+
+    if state.name_entry().is_some() {
+        handle_name_entry_input(state, ctx);
+    } else {
+        handle_menu_input(state, ctx);
+    }
+}
+
+/// Draws the results of the run that just ended, with the name prompt
+/// when it made the high scores.
+pub fn render_game_over_menu(state: &GameState, ctx: &mut dyn Renderer) {
     render_menu_background(ctx);
 
-    ctx.print_centered(MENU_TITLE_Y, "Game Over");
+    if let Some(network) = state.network() {
+        let remote = network.remote();
+        let title = match (network.status(), state.versus_winner()) {
+            (PeerStatus::Desynced { tick }, _) => format!("Desync at tick {}: no result", tick),
+            (PeerStatus::Disconnected, _) if !remote.is_game_over() => {
+                "Your rival disconnected".to_string()
+            }
+            (_, Some(Seat::One)) => "You win!".to_string(),
+            (_, Some(Seat::Two)) => "Your rival wins!".to_string(),
+            (_, None) => "It's a draw!".to_string(),
+        };
+        ctx.print_centered(MENU_TITLE_Y, &title);
+        ctx.print_centered(
+            MENU_SCORE_Y,
+            &format!(
+                "You: {} points  Rival: {} points",
+                state.score(),
+                remote.score()
+            ),
+        );
+    } else if let Some(versus) = state.versus() {
+        let title = match state.versus_winner() {
+            Some(seat) => format!("{} wins!", seat),
+            None => "It's a draw!".to_string(),
+        };
+        ctx.print_centered(MENU_TITLE_Y, &title);
+        ctx.print_centered(
+            MENU_SCORE_Y,
+            &format!(
+                "{}: {} points  {}: {} points",
+                Seat::One,
+                state.score(),
+                Seat::Two,
+                versus.simulation().score()
+            ),
+        );
+    } else {
+        let title = if state.is_replaying() {
+            "Replay Finished"
+        } else {
+            "Game Over"
+        };
+        ctx.print_centered(MENU_TITLE_Y, title);
+        ctx.print_centered(
+            MENU_SCORE_Y,
+            &format!("You earned {} points.", state.score()),
+        );
+    }
+    let rivals: Vec<String> = state
+        .opponent()
+        .map(|opponent| {
+            format!(
+                "{} bot: {}",
+                opponent.agent().name(),
+                opponent.simulation().score()
+            )
+        })
+        .into_iter()
+        .chain(
+            state
+                .ghost()
+                .map(|ghost| format!("Ghost: {}", ghost.replay().final_score)),
+        )
+        .collect();
+    if !rivals.is_empty() {
+        ctx.print_centered(MENU_SCORE_Y + 1, &rivals.join("  "));
+    }
+
+    if let Some(name) = state.name_entry() {
+        ctx.print_centered(MENU_OPTIONS_START_Y, "New high score! Enter your name:");
+        ctx.print_centered(MENU_OPTIONS_START_Y + 1, &format!("{}_", name));
+        ctx.print_centered(MENU_OPTIONS_START_Y + 2, "▌ENTER▐ Save");
+        return;
+    }
+
+    let options = [
+        state.can_restart().then_some("▌P▐ Play again"),
+        state.can_restart().then_some("▌H▐ High scores"),
+        Some("▌Q▐ Quit game"),
+    ];
+    for (line, option) in (MENU_OPTIONS_START_Y..).zip(options.into_iter().flatten()) {
+        ctx.print_centered(line, option);
+    }
     ctx.print_centered(
-        MENU_SCORE_Y,
-        format!("You earned {} points.", state.score()),
+        MENU_INFO_START_Y,
+        &format!("Seed: {}  Mode: {}", state.seed(), state.play_mode()),
     );
-    ctx.print_centered(MENU_OPTIONS_START_Y, "▌P▐ Play again");
-    ctx.print_centered(MENU_OPTIONS_START_Y + 1, "▌Q▐ Quit game");
 
-    handle_menu_input(state, ctx);
+    let messages = [state.high_score_message(), state.replay_message()];
+    for (line, message) in (MENU_INFO_START_Y + 1..).zip(messages.into_iter().flatten()) {
+        ctx.print_centered(line, message);
+    }
+}
+
+pub fn high_scores_menu(state: &mut GameState, ctx: &mut BTerm) {
+    render_high_scores_menu(state, ctx);
+
+    if let Some(VirtualKeyCode::Escape) = ctx.key {
+        state.close_high_scores();
+    }
+}
+
+pub fn render_high_scores_menu(state: &GameState, ctx: &mut dyn Renderer) {
+    render_menu_background(ctx);
+
+    ctx.print_centered(MENU_TITLE_Y, "High Scores");
+
+    let entries = state.high_scores().entries();
+    if entries.is_empty() {
+        ctx.print_centered(HIGH_SCORES_LIST_START_Y, "No high scores yet.");
+    }
+
+    for ((line, rank), entry) in (HIGH_SCORES_LIST_START_Y..).zip(1..).zip(entries) {
+        ctx.print_centered(
+            line,
+            &format!(
+                "{:>2}. {:<12} {:>5} {:<8} {}",
+                rank, entry.name, entry.score, entry.mode, entry.date
+            ),
+        );
+    }
+
+    let footer_y = HIGH_SCORES_LIST_START_Y + entries.len().max(1) as i32 + 1;
+    ctx.print_centered(footer_y, "▌ESC▐ Back");
+
+    if let Some(error) = state.high_score_load_error() {
+        ctx.print_centered(footer_y + 2, "Saved high scores could not be loaded:");
+        let width = (state.config().display.screen_width as usize).saturating_sub(2);
+        for (line, text) in (footer_y + 3..).zip(wrap_words(error, width)) {
+            ctx.print_centered(line, &text);
+        }
+    }
+}
+
+pub fn characters_menu(state: &mut GameState, ctx: &mut BTerm) {
+    render_characters_menu(state, ctx);
+
+    match ctx.key {
+        Some(VirtualKeyCode::Escape | VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter) => {
+            state.set_mode(GameMode::Menu)
+        }
+        Some(key) => {
+            if let Some(index) = CHARACTER_KEYS.iter().position(|&code| code == key) {
+                state.select_skin(index);
+            }
+        }
+        None => {}
+    }
+}
+
+/// Lists the characters to pick from, marking the one being played, with
+/// a preview of it underneath.
+pub fn render_characters_menu(state: &GameState, ctx: &mut dyn Renderer) {
+    render_menu_background(ctx);
+
+    ctx.print_centered(MENU_TITLE_Y, "Characters");
+
+    let skins = &state.skins().skins()[..state.skins().skins().len().min(CHARACTER_KEYS.len())];
+    for ((line, number), skin) in (CHARACTERS_LIST_START_Y..).zip(1..).zip(skins) {
+        let marker = if skin.name == state.skin().name {
+            '*'
+        } else {
+            ' '
+        };
+        ctx.print_centered(line, &format!("▌{}▐ {:<16}{}", number, skin.name, marker));
+    }
+
+    let footer_y = CHARACTERS_LIST_START_Y + skins.len() as i32 + 1;
+    ctx.print_centered(footer_y, "▌ESC▐ Back");
+
+    let skin = state.skin();
+    let (screen_width, _) = ctx.get_char_size();
+    let glyph = skin.sprite_indices().first().copied().unwrap_or_default();
+    ctx.set_active_console(SPRITE_LAYER);
+    ctx.set_fancy(
+        PointF::new(
+            screen_width as f32 / 2.0,
+            footer_y as f32 + CHARACTERS_PREVIEW_OFFSET_Y,
+        ),
+        1,
+        Degrees::new(0.0),
+        PointF::new(skin.render_scale, skin.render_scale),
+        skin.tint(),
+        NAVY.into(),
+        glyph,
+    );
+    ctx.set_active_console(TEXT_LAYER);
+}
+
+pub fn pause_menu(state: &mut GameState, ctx: &mut BTerm) {
+    state.poll_network();
+    render_pause_menu(state, ctx);
+
+    if let Some(key) = ctx.key {
+        match key {
+            VirtualKeyCode::P | VirtualKeyCode::Escape => state.resume(),
+            VirtualKeyCode::R if state.can_restart() => state.reset_game(),
+            VirtualKeyCode::Q => state.quit_to_menu(),
+            _ => {}
+        }
+    }
+}
+
+/// Draws the paused run with the pause options on top.
+pub fn render_pause_menu(state: &GameState, ctx: &mut dyn Renderer) {
+    render_game_frame(state, ctx);
+
+    ctx.print_centered(MENU_TITLE_Y, "Paused");
+    let options = [
+        Some("▌P▐ Resume"),
+        state.can_restart().then_some("▌R▐ Restart"),
+        Some("▌Q▐ Quit to menu"),
+    ];
+    for (line, option) in (MENU_OPTIONS_START_Y..).zip(options.into_iter().flatten()) {
+        ctx.print_centered(line, option);
+    }
 }
 
-fn render_menu_background(ctx: &mut BTerm) {
+/// Splits `text` into lines of at most `width` characters, breaking
+/// between words where it can.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+/// Clears the screen, including any sprite left by the attract demo.
+fn render_menu_background(ctx: &mut dyn Renderer) {
+    ctx.set_active_console(SPRITE_LAYER);
+    ctx.cls();
+    ctx.set_active_console(TEXT_LAYER);
     ctx.cls();
 }
 
 fn handle_menu_input(state: &mut GameState, ctx: &mut BTerm) {
     if let Some(key) = ctx.key {
         match key {
-            VirtualKeyCode::P => state.reset_game(),
+            VirtualKeyCode::P if state.can_restart() => state.reset_game(),
+            VirtualKeyCode::H if state.can_restart() => state.show_high_scores(),
             VirtualKeyCode::Q => ctx.quitting = true,
             _ => {}
         }
     }
 }
+
+fn handle_name_entry_input(state: &mut GameState, ctx: &mut BTerm) {
+    let Some(key) = ctx.key else {
+        return;
+    };
+
+    match key {
+        VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => state.submit_high_score(),
+        // Terminals report Backspace as Delete.
+        VirtualKeyCode::Back | VirtualKeyCode::Delete => state.pop_name_char(),
+        VirtualKeyCode::Space => state.push_name_char(' '),
+        _ => {
+            if let Some(&(_, c)) = NAME_ENTRY_KEYS.iter().find(|(code, _)| *code == key) {
+                state.push_name_char(c);
+            }
+        }
+    }
+}
@@ -1,7 +1,41 @@
+mod attract;
+mod difficulty;
 mod game_mode;
+mod ghost;
+mod high_scores;
 mod menu;
+mod opponent;
+mod play_mode;
+mod reachability;
+mod replay;
+mod simulation;
+mod spectator;
 mod state;
 mod transitions;
+mod versus;
 
+pub use attract::AttractMode;
+pub use difficulty::{
+    CurveShape, DifficultyBounds, DifficultyCurve, DifficultyParams, DifficultyPreset, LinearCurve,
+    LogisticCurve, SteppedCurve,
+};
 pub use game_mode::GameMode;
+pub use ghost::{Ghost, GhostLead};
+pub use high_scores::{HighScoreEntry, HighScoreTable, default_high_score_path};
+pub use menu::{
+    render_characters_menu, render_game_over_menu, render_high_scores_menu, render_main_menu,
+    render_pause_menu,
+};
+pub use opponent::Opponent;
+pub use play_mode::PlayMode;
+pub use reachability::ReachabilityPlanner;
+pub(crate) use reachability::thin_out;
+pub use replay::{
+    REPLAY_FORMAT_VERSION, Replay, ReplayPlayback, ReplayRecorder, default_replay_dir,
+    find_best_replay, new_replay_path, replay_file_name,
+};
+pub use simulation::{Simulation, TickInput, TickOutcome, random_seed};
+pub use spectator::SpectatorMode;
 pub use state::{GameState, GameTimer, Score};
+pub use transitions::{render_game_frame, render_simulation, render_world};
+pub use versus::{Seat, Versus, race_winner};
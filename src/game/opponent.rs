@@ -0,0 +1,46 @@
+use crate::agent::{Agent, Observation};
+use crate::config::GameConfig;
+use crate::game::Simulation;
+
+/// A bot racing the player on the same course.
+///
+/// The opponent plays its own [`Simulation`] of the player's seed, stepped
+/// in lockstep with the player's run, and keeps flying until it crashes.
+#[derive(Debug)]
+pub struct Opponent {
+    agent: Box<dyn Agent>,
+    simulation: Simulation,
+}
+
+impl Opponent {
+    /// Creates an opponent that lets `agent` play the course of `seed`.
+    pub fn new(mut agent: Box<dyn Agent>, config: GameConfig, seed: u64) -> Self {
+        agent.reset();
+        Self {
+            agent,
+            simulation: Simulation::with_config(config, seed),
+        }
+    }
+
+    pub fn agent(&self) -> &dyn Agent {
+        self.agent.as_ref()
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    /// Starts the opponent again on the course of `seed`.
+    pub fn restart(&mut self, config: GameConfig, seed: u64) {
+        self.agent.reset();
+        self.simulation = Simulation::with_config(config, seed);
+    }
+
+    /// Plays one tick, unless the opponent has already crashed.
+    pub fn step(&mut self) {
+        if !self.simulation.is_game_over() {
+            let input = self.agent.act(&Observation::of(&self.simulation));
+            self.simulation.step(input);
+        }
+    }
+}
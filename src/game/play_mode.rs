@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Rule set a run is played under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayMode {
+    /// Classic rules: touching an obstacle or leaving the screen ends the run.
+    #[default]
+    Endless,
+    /// Obstacles can be flown through; only leaving the screen ends the run.
+    Practice,
+}
+
+impl PlayMode {
+    /// Every play mode, in menu order.
+    pub const ALL: [PlayMode; 2] = [PlayMode::Endless, PlayMode::Practice];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayMode::Endless => "endless",
+            PlayMode::Practice => "practice",
+        }
+    }
+
+    /// Whether runs in this mode can earn a place in the high-score table.
+    pub fn is_ranked(&self) -> bool {
+        matches!(self, PlayMode::Endless)
+    }
+
+    /// Whether touching an obstacle ends the run.
+    pub fn obstacles_are_lethal(&self) -> bool {
+        matches!(self, PlayMode::Endless)
+    }
+}
+
+impl fmt::Display for PlayMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PlayMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(PlayMode::name).collect();
+                format!(
+                    "unknown mode '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
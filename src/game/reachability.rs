@@ -0,0 +1,222 @@
+//! Reachability analysis for generated courses.
+//!
+//! A random gap can sit too far from the previous one for any sequence of
+//! flaps to reach it. [`ReachabilityPlanner`] follows every state a
+//! perfect player could be in, using the real player physics, so the
+//! course generator can check each new obstacle before placing it.
+
+use crate::config::{REACHABILITY_MAX_STATES, REACHABILITY_STATE_PRECISION};
+use crate::environment::Obstacle;
+use crate::game::DifficultyCurve;
+use crate::player::{Player, Position};
+
+/// Every state a perfect player can be in at one tick of a run.
+///
+/// Only flapping differs between the states, so they share the tick,
+/// horizontal position and score and are stored as a height and a
+/// vertical velocity. States closer together than one part in
+/// [`REACHABILITY_STATE_PRECISION`] are merged, and at most
+/// [`REACHABILITY_MAX_STATES`] of them, spread evenly over the heights
+/// and velocities reached, are followed. The planner may therefore miss a
+/// way through, but never reports one that does not exist.
+#[derive(Debug, Clone)]
+pub struct ReachabilityPlanner {
+    template: Player,
+    states: Vec<(f32, f32)>,
+    tick: u64,
+    score: i32,
+}
+
+impl ReachabilityPlanner {
+    /// Starts planning from a single known player state.
+    pub fn new(player: &Player, tick: u64, score: i32) -> Self {
+        Self {
+            template: player.clone(),
+            states: vec![(player.y(), player.velocity())],
+            tick,
+            score,
+        }
+    }
+
+    /// Returns `true` if no state is reachable.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Number of distinct reachable states.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Reachable player states, in no particular order.
+    pub fn states(&self) -> impl Iterator<Item = Player> + '_ {
+        self.states.iter().map(|&(y, velocity)| {
+            let mut player = self.template.clone();
+            player.set_motion(Position::new(self.template.x(), y), velocity);
+            player
+        })
+    }
+
+    /// Tick the states are reached at.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Score the player has on reaching the states.
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// States on the last tick before the player comes level with
+    /// column `x`.
+    ///
+    /// Nothing can be hit before then, so every obstacle at `x` can be
+    /// checked from these states instead of repeating the approach.
+    /// Returns `None` if the player cannot stay on screen that long.
+    pub fn approach(&self, x: i32, difficulty: &dyn DifficultyCurve) -> Option<Self> {
+        let mut current = self.clone();
+
+        loop {
+            let next = current.step(None, false, difficulty);
+            if next.is_empty() {
+                return None;
+            }
+            if next.template.hitbox().right() > x as f32 {
+                return Some(current);
+            }
+            current = next;
+        }
+    }
+
+    /// States reachable once the player has cleared `obstacle` without
+    /// touching it or leaving the screen.
+    ///
+    /// `spawn_tick` is the tick the obstacle appeared on, which sets how
+    /// far moving obstacles have moved by the time the player gets there.
+    /// Returns `None` if no state gets through.
+    pub fn through(
+        &self,
+        obstacle: &Obstacle,
+        spawn_tick: u64,
+        difficulty: &dyn DifficultyCurve,
+    ) -> Option<Self> {
+        self.advance_past(obstacle, spawn_tick, true, difficulty)
+    }
+
+    /// States reachable once the player is past `obstacle`, treating it as
+    /// if it were not there.
+    ///
+    /// Returns `None` only if the player cannot stay on screen.
+    pub fn ignoring(
+        &self,
+        obstacle: &Obstacle,
+        spawn_tick: u64,
+        difficulty: &dyn DifficultyCurve,
+    ) -> Option<Self> {
+        self.advance_past(obstacle, spawn_tick, false, difficulty)
+    }
+
+    fn advance_past(
+        &self,
+        obstacle: &Obstacle,
+        spawn_tick: u64,
+        lethal: bool,
+        difficulty: &dyn DifficultyCurve,
+    ) -> Option<Self> {
+        let mut obstacle = obstacle.clone();
+        let mut current = self.clone();
+
+        loop {
+            if current.is_empty() {
+                return None;
+            }
+            if has_passed(&current.template, &obstacle) {
+                return Some(current);
+            }
+
+            let age = (current.tick + 1).saturating_sub(spawn_tick);
+            obstacle.set_age(u32::try_from(age).unwrap_or(u32::MAX));
+            current = current.step(Some(&obstacle), lethal, difficulty);
+        }
+    }
+
+    /// Advances every state by one tick with and without a flap.
+    ///
+    /// States that leave the screen are dropped, as are those that touch
+    /// `obstacle` if it is `lethal`. Clearing `obstacle` scores a point.
+    fn step(
+        &self,
+        obstacle: Option<&Obstacle>,
+        lethal: bool,
+        difficulty: &dyn DifficultyCurve,
+    ) -> Self {
+        let mut player = self.template.clone();
+        let mut reached = Vec::with_capacity(self.states.len() * 2);
+
+        for &(y, velocity) in &self.states {
+            for flap in [false, true] {
+                player.set_motion(Position::new(self.template.x(), y), velocity);
+                if flap {
+                    player.flap();
+                }
+                player.update_physics();
+
+                let crashed = player.is_out_of_bounds()
+                    || obstacle.is_some_and(|obstacle| {
+                        lethal
+                            && is_level_with(&player, obstacle)
+                            && obstacle.has_collision_with(&player)
+                    });
+                if !crashed {
+                    reached.push((state_key(&player), player.y(), player.velocity()));
+                }
+            }
+        }
+
+        reached.sort_unstable_by_key(|&(key, _, _)| key);
+        reached.dedup_by_key(|&mut (key, _, _)| key);
+        let states = thin_out(&reached, REACHABILITY_MAX_STATES)
+            .map(|&(_, y, velocity)| (y, velocity))
+            .collect();
+
+        let mut template = self.template.clone();
+        template.update_physics();
+        let tick = self.tick + 1;
+        let passed = obstacle.is_some_and(|obstacle| has_passed(&template, obstacle));
+        let score = self.score + i32::from(passed);
+        let speed = difficulty.params(score, tick).scroll_speed.max(1);
+        template.set_movement_speed(speed);
+
+        Self {
+            template,
+            states,
+            tick,
+            score,
+        }
+    }
+}
+
+/// Whether the player's hitbox has fully cleared `obstacle`.
+fn has_passed(player: &Player, obstacle: &Obstacle) -> bool {
+    obstacle.right() as f32 <= player.full_hitbox().left()
+}
+
+/// Whether the player's hitbox spanned any of the obstacle's columns
+/// during its last move.
+fn is_level_with(player: &Player, obstacle: &Obstacle) -> bool {
+    let hitbox = player.swept_hitbox();
+    hitbox.left() < obstacle.right() as f32 && (obstacle.x() as f32) < hitbox.right()
+}
+
+/// Identifies states that behave the same from here on.
+fn state_key(player: &Player) -> (i64, i64) {
+    let quantize = |value: f32| (value * REACHABILITY_STATE_PRECISION).round() as i64;
+    (quantize(player.y()), quantize(player.velocity()))
+}
+
+/// At most `limit` of `items`, picked at even intervals so they span the
+/// whole slice.
+pub(crate) fn thin_out<T>(items: &[T], limit: usize) -> impl Iterator<Item = &T> {
+    let kept = items.len().min(limit);
+    (0..kept).map(move |index| &items[index * items.len() / kept])
+}
@@ -0,0 +1,320 @@
+//! Run recording and playback.
+//!
+//! A replay stores everything needed to reproduce a run exactly: the seed
+//! of the obstacle course, the configuration it was played with, and the
+//! tick index of every flap. Feeding those flaps back through
+//! [`Simulation::step`] reaches the same final state as the original run.
+
+use crate::config::{DATA_DIR_NAME, GameConfig, REPLAY_DIR_NAME};
+use crate::error::{GameError, GameResult};
+use crate::game::{Simulation, TickInput};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the replay file format written by this build.
+pub const REPLAY_FORMAT_VERSION: u32 = 7;
+
+/// A complete, serializable record of a single run.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{ReplayRecorder, Simulation, TickInput};
+///
+/// let mut simulation = Simulation::with_seed(7);
+/// let mut recorder = ReplayRecorder::new(simulation.seed(), GameConfig::default());
+///
+/// while !simulation.is_game_over() {
+///     let input = if simulation.tick() % 5 == 0 { TickInput::FLAP } else { TickInput::IDLE };
+///     recorder.record(simulation.tick(), input);
+///     simulation.step(input);
+/// }
+///
+/// let replay = recorder.finish(&simulation);
+/// assert!(replay.verify());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// File format version, checked when loading.
+    pub version: u32,
+    /// Seed of the run's obstacle course.
+    pub seed: u64,
+    /// Total number of ticks the run lasted.
+    pub ticks: u64,
+    /// Score reached at the end of the run.
+    pub final_score: i32,
+    /// Tick indices, in increasing order, on which the player flapped.
+    pub flaps: Vec<u64>,
+    /// Configuration the run was played with.
+    pub config: GameConfig,
+}
+
+impl Replay {
+    /// Reads a replay from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file cannot be read,
+    /// `GameError::InvalidReplay` if it is malformed or was written by an
+    /// incompatible version of the game, and the matching validation error
+    /// if its configuration is not playable.
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        let invalid = |reason: String| GameError::InvalidReplay {
+            path: path.display().to_string(),
+            reason,
+        };
+
+        let replay: Self = toml::from_str(&contents).map_err(|e| invalid(e.to_string()))?;
+
+        if replay.version != REPLAY_FORMAT_VERSION {
+            return Err(invalid(format!(
+                "unsupported format version {} (expected {})",
+                replay.version, REPLAY_FORMAT_VERSION
+            )));
+        }
+
+        if !replay.flaps.is_sorted_by(|a, b| a < b) {
+            return Err(invalid("flap ticks are not strictly increasing".into()));
+        }
+
+        replay.config.validate()?;
+        Ok(replay)
+    }
+
+    /// Writes the replay to a TOML file, creating parent directories as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file or its directory cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> GameResult<()> {
+        let path = path.as_ref();
+        let io_error = |source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let contents = toml::to_string(self).map_err(|e| GameError::InvalidReplay {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        fs::write(path, contents).map_err(io_error)
+    }
+
+    /// Plays the replay back without a window and returns the final state.
+    ///
+    /// Playback stops when the run ends or when the recorded tick count is
+    /// reached, whichever comes first.
+    pub fn simulate(&self) -> Simulation {
+        let mut simulation = Simulation::with_config(self.config.clone(), self.seed);
+        let mut playback = ReplayPlayback::new(self.clone());
+
+        while !simulation.is_game_over() && simulation.tick() < self.ticks {
+            let input = playback.next_input(simulation.tick());
+            simulation.step(input);
+        }
+
+        simulation
+    }
+
+    /// Returns `true` if playing the replay back reaches the recorded score.
+    pub fn verify(&self) -> bool {
+        self.simulate().score() == self.final_score
+    }
+}
+
+/// Collects the inputs of a run as it is played.
+#[derive(Debug, Clone)]
+pub struct ReplayRecorder {
+    seed: u64,
+    config: GameConfig,
+    flaps: Vec<u64>,
+}
+
+impl ReplayRecorder {
+    pub fn new(seed: u64, config: GameConfig) -> Self {
+        Self {
+            seed,
+            config,
+            flaps: Vec::new(),
+        }
+    }
+
+    /// Records the input applied on the tick with index `tick`.
+    pub fn record(&mut self, tick: u64, input: TickInput) {
+        if input.flap {
+            self.flaps.push(tick);
+        }
+    }
+
+    /// Completes the recording using the final state of the run.
+    pub fn finish(self, simulation: &Simulation) -> Replay {
+        Replay {
+            version: REPLAY_FORMAT_VERSION,
+            seed: self.seed,
+            ticks: simulation.tick(),
+            final_score: simulation.score(),
+            flaps: self.flaps,
+            config: self.config,
+        }
+    }
+}
+
+/// Feeds the inputs of a recorded run back into a simulation.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    next_flap: usize,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_flap: 0,
+        }
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Returns the recorded input for the tick with index `tick`.
+    ///
+    /// Ticks must be requested in increasing order.
+    pub fn next_input(&mut self, tick: u64) -> TickInput {
+        while self
+            .replay
+            .flaps
+            .get(self.next_flap)
+            .is_some_and(|&flap| flap < tick)
+        {
+            self.next_flap += 1;
+        }
+
+        if self.replay.flaps.get(self.next_flap) == Some(&tick) {
+            self.next_flap += 1;
+            TickInput::FLAP
+        } else {
+            TickInput::IDLE
+        }
+    }
+
+    /// Returns `true` once every recorded tick has been played.
+    pub fn is_finished(&self, tick: u64) -> bool {
+        tick >= self.replay.ticks
+    }
+}
+
+/// Default directory for saved replays, inside the user's data directory.
+pub fn default_replay_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DATA_DIR_NAME).join(REPLAY_DIR_NAME))
+}
+
+/// Builds a file name for a replay of a run with the given seed, stamped
+/// with the current time in milliseconds.
+pub fn replay_file_name(seed: u64) -> String {
+    format!("{}-{seed}.toml", replay_timestamp())
+}
+
+/// Builds a path in `dir` for a new replay of a run with the given seed
+/// that no file there has yet.
+///
+/// Runs of the same seed that end within the same millisecond get a
+/// counter after the timestamp, so no saved replay is overwritten.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{ReplayRecorder, Simulation, new_replay_path};
+///
+/// let dir = std::env::temp_dir().join("flappycheems-doc-new-replay-path");
+/// let replay = ReplayRecorder::new(3, GameConfig::default()).finish(&Simulation::with_seed(3));
+///
+/// let first = new_replay_path(&dir, 3);
+/// replay.save(&first)?;
+/// let second = new_replay_path(&dir, 3);
+/// assert_ne!(first, second);
+/// assert!(second.to_string_lossy().ends_with("-3.toml"));
+/// # std::fs::remove_dir_all(&dir).ok();
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+pub fn new_replay_path(dir: impl AsRef<Path>, seed: u64) -> PathBuf {
+    let dir = dir.as_ref();
+    let timestamp = replay_timestamp();
+    let mut path = dir.join(format!("{timestamp}-{seed}.toml"));
+
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{timestamp}-{counter}-{seed}.toml"));
+        counter += 1;
+    }
+
+    path
+}
+
+/// Milliseconds since the Unix epoch, which replay file names start with.
+fn replay_timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default()
+}
+
+/// Finds the highest-scoring replay in `dir` of the course `seed` generates
+/// under `config`.
+///
+/// Only files named by [`replay_file_name`] or [`new_replay_path`] for
+/// `seed` are considered.
+/// Files that cannot be read or are not valid replays are skipped, as is
+/// a missing directory.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{ReplayRecorder, Simulation, find_best_replay, replay_file_name};
+///
+/// let dir = std::env::temp_dir().join("flappycheems-doc-best-replay");
+/// let config = GameConfig::default();
+/// let simulation = Simulation::with_seed(3);
+///
+/// ReplayRecorder::new(3, config.clone())
+///     .finish(&simulation)
+///     .save(dir.join(replay_file_name(3)))?;
+///
+/// assert_eq!(find_best_replay(&dir, 3, &config).map(|replay| replay.seed), Some(3));
+/// assert!(find_best_replay(&dir, 4, &config).is_none());
+/// # std::fs::remove_dir_all(&dir).ok();
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+pub fn find_best_replay(dir: impl AsRef<Path>, seed: u64, config: &GameConfig) -> Option<Replay> {
+    let entries = fs::read_dir(dir).ok()?;
+    let suffix = format!("-{seed}.toml");
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(&suffix))
+        })
+        .filter_map(|path| Replay::load(path).ok())
+        .filter(|replay| replay.seed == seed && replay.config == *config)
+        .max_by_key(|replay| replay.final_score)
+}
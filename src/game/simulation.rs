@@ -0,0 +1,556 @@
+//! Headless game simulation.
+//!
+//! This module advances a run one physics tick at a time without touching
+//! the terminal, so games can be stepped by tests, bots and batch runners
+//! on machines that have no window system.
+
+use crate::config::{GameConfig, OBSTACLE_GENERATION_ATTEMPTS};
+use crate::environment::{Obstacle, ObstacleGap, Pickup, PickupKind};
+use crate::game::{DifficultyCurve, DifficultyParams, ReachabilityPlanner, Score};
+use crate::player::Player;
+use bracket_lib::prelude::RandomNumberGenerator;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+/// Player input sampled for a single simulation tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TickInput {
+    /// Whether the player flapped since the previous tick.
+    pub flap: bool,
+}
+
+impl TickInput {
+    /// Input for a tick in which the player flaps.
+    pub const FLAP: Self = Self { flap: true };
+
+    /// Input for a tick in which the player does nothing.
+    pub const IDLE: Self = Self { flap: false };
+}
+
+/// Result of advancing the simulation by one tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickOutcome {
+    /// The run continues with nothing notable happening.
+    Running,
+    /// The player passed at least one obstacle and earned points.
+    Scored,
+    /// The player crashed; further steps have no effect.
+    GameOver,
+}
+
+/// The complete, renderer-independent state of a single run.
+///
+/// `Simulation` owns the player, the obstacle course, the score and the
+/// random number generator that lays out the course, and advances them in
+/// fixed ticks through [`Simulation::step`].
+///
+/// The course is a queue of obstacles laid out by a [`DifficultyCurve`],
+/// which sets each obstacle's gap, spacing and kind as well as the
+/// player's scroll speed. New obstacles are spawned just beyond the right
+/// edge of the screen and dropped once the player's hitbox has fully
+/// cleared them.
+///
+/// Every obstacle is checked with a [`ReachabilityPlanner`] before it is
+/// placed, so a perfect player can always get through the course.
+/// Unreachable obstacles are rolled again and, failing that, replaced by
+/// the closest reachable static gap.
+///
+/// Once an obstacle is placed, the same random number generator decides
+/// whether a [`Pickup`] waits in its gap, so power-ups are part of the
+/// course a seed lays out. Difficulty follows the obstacles passed rather
+/// than the score, which the score multiplier can inflate.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::game::{Simulation, TickInput, TickOutcome};
+///
+/// let mut simulation = Simulation::new();
+/// while simulation.step(TickInput::IDLE) != TickOutcome::GameOver {}
+///
+/// assert!(simulation.is_game_over());
+/// ```
+#[derive(Clone)]
+pub struct Simulation {
+    player: Player,
+    obstacles: VecDeque<Obstacle>,
+    obstacles_spawned: i32,
+    obstacles_passed: i32,
+    pickups: VecDeque<Pickup>,
+    score: Score,
+    config: GameConfig,
+    difficulty: Arc<dyn DifficultyCurve>,
+    planner: ReachabilityPlanner,
+    rng: RandomNumberGenerator,
+    seed: u64,
+    tick: u64,
+    game_over: bool,
+}
+
+impl Simulation {
+    /// Creates a simulation with a freshly drawn random seed.
+    pub fn new() -> Self {
+        Self::with_seed(random_seed())
+    }
+
+    /// Creates a simulation whose obstacle course is fully determined by `seed`.
+    ///
+    /// Two simulations built from the same seed and fed the same inputs
+    /// produce identical runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::game::{Simulation, TickInput};
+    ///
+    /// let mut first = Simulation::with_seed(42);
+    /// let mut second = Simulation::with_seed(42);
+    ///
+    /// for tick in 0..200 {
+    ///     let input = if tick % 4 == 0 { TickInput::FLAP } else { TickInput::IDLE };
+    ///     assert_eq!(first.step(input), second.step(input));
+    ///     assert!(first.obstacles().iter().eq(second.obstacles().iter()));
+    /// }
+    /// ```
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_config(GameConfig::default(), seed)
+    }
+
+    /// Creates a simulation that plays by `config` on the course of `seed`.
+    ///
+    /// The difficulty curve is the one selected by `config.difficulty`.
+    pub fn with_config(config: GameConfig, seed: u64) -> Self {
+        let difficulty = config.difficulty_curve();
+        Self::with_difficulty(config, seed, difficulty)
+    }
+
+    /// Creates a simulation whose course is laid out by a custom
+    /// difficulty curve.
+    ///
+    /// Runs played this way can only be replayed with the same curve.
+    pub fn with_difficulty(
+        config: GameConfig,
+        seed: u64,
+        difficulty: Arc<dyn DifficultyCurve>,
+    ) -> Self {
+        let mut player = Player::new(&config);
+        player.set_movement_speed(difficulty.params(0, 0).scroll_speed.max(1));
+
+        let mut simulation = Self {
+            planner: ReachabilityPlanner::new(&player, 0, 0),
+            player,
+            obstacles: VecDeque::new(),
+            obstacles_spawned: 0,
+            obstacles_passed: 0,
+            pickups: VecDeque::new(),
+            score: Score::new(),
+            rng: RandomNumberGenerator::seeded(seed),
+            config,
+            difficulty,
+            seed,
+            tick: 0,
+            game_over: false,
+        };
+
+        simulation.spawn_obstacles();
+        simulation
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Seed that generated this run's obstacle course.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    /// Obstacles currently in the world, ordered from left to right.
+    pub fn obstacles(&self) -> &VecDeque<Obstacle> {
+        &self.obstacles
+    }
+
+    /// The closest obstacle the player has not yet passed.
+    pub fn next_obstacle(&self) -> Option<&Obstacle> {
+        self.obstacles.front()
+    }
+
+    /// Pickups waiting on the course, ordered from left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::config::GameConfig;
+    /// use flappycheems::game::Simulation;
+    ///
+    /// let mut config = GameConfig::default();
+    /// config.power_ups.spawn_chance = 100;
+    /// let simulation = Simulation::with_config(config.clone(), 9);
+    /// assert_eq!(simulation.pickups().len(), simulation.obstacles().len());
+    /// assert!(simulation.pickups().iter().eq(Simulation::with_config(config.clone(), 9).pickups()));
+    ///
+    /// config.power_ups.spawn_chance = 0;
+    /// assert!(Simulation::with_config(config, 9).pickups().is_empty());
+    /// ```
+    pub fn pickups(&self) -> &VecDeque<Pickup> {
+        &self.pickups
+    }
+
+    /// Leftmost world column visible on screen.
+    pub fn camera_x(&self) -> i32 {
+        self.player.x()
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score.get()
+    }
+
+    /// Number of obstacles the player has cleared, whatever they scored.
+    pub fn obstacles_passed(&self) -> i32 {
+        self.obstacles_passed
+    }
+
+    /// Number of ticks that have been simulated so far.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// The curve laying out this run's course.
+    pub fn difficulty(&self) -> &Arc<dyn DifficultyCurve> {
+        &self.difficulty
+    }
+
+    /// A fingerprint of the run's current state.
+    ///
+    /// Two runs that have played the same course with the same inputs
+    /// have the same hash, on any machine running the same build, so
+    /// comparing hashes detects runs that have drifted apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flappycheems::game::{Simulation, TickInput};
+    ///
+    /// let mut first = Simulation::with_seed(5);
+    /// let mut second = Simulation::with_seed(5);
+    /// assert_eq!(first.state_hash(), second.state_hash());
+    ///
+    /// first.step(TickInput::FLAP);
+    /// second.step(TickInput::IDLE);
+    /// assert_ne!(first.state_hash(), second.state_hash());
+    /// ```
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = StateHasher::new();
+        hash.write(&self.tick.to_le_bytes());
+        hash.write(&self.score().to_le_bytes());
+        hash.write(&[u8::from(self.game_over)]);
+        hash.write(&self.player.x().to_le_bytes());
+        hash.write(&self.player.y().to_bits().to_le_bytes());
+        hash.write(&self.player.velocity().to_bits().to_le_bytes());
+
+        let effects = self.player.effects();
+        for kind in PickupKind::ALL {
+            hash.write(&effects.remaining(kind).to_le_bytes());
+        }
+        if let Some(x) = effects.shielded_obstacle() {
+            hash.write(&x.to_le_bytes());
+        }
+
+        for obstacle in &self.obstacles {
+            hash.write(&obstacle.x().to_le_bytes());
+            hash.write(obstacle.kind().name().as_bytes());
+            hash.write(&obstacle.age().to_le_bytes());
+            for gap in std::iter::once(obstacle.gap()).chain(obstacle.second_gap()) {
+                hash.write(&gap.center_y().to_le_bytes());
+                hash.write(&gap.half_size().to_le_bytes());
+            }
+        }
+
+        for pickup in &self.pickups {
+            hash.write(&pickup.x().to_le_bytes());
+            hash.write(&pickup.y().to_le_bytes());
+            hash.write(pickup.kind().name().as_bytes());
+        }
+
+        hash.finish()
+    }
+
+    /// Advances the run by one physics tick.
+    ///
+    /// Applies the input, moves the player, collects any pickup it touches,
+    /// scores any passed obstacle and checks for a crash. Once the run is
+    /// over this is a no-op that keeps returning [`TickOutcome::GameOver`].
+    pub fn step(&mut self, input: TickInput) -> TickOutcome {
+        if self.game_over {
+            return TickOutcome::GameOver;
+        }
+
+        if input.flap {
+            self.player.flap();
+        }
+        self.player.update_physics();
+        for obstacle in &mut self.obstacles {
+            obstacle.update();
+        }
+        self.tick += 1;
+
+        self.collect_pickups();
+        let passed = self.despawn_passed_obstacles();
+        self.apply_scroll_speed();
+        self.spawn_obstacles();
+
+        if self.has_crashed() {
+            self.game_over = true;
+            TickOutcome::GameOver
+        } else if passed > 0 {
+            TickOutcome::Scored
+        } else {
+            TickOutcome::Running
+        }
+    }
+
+    /// Starts the effect of every pickup the player touched on its last
+    /// move and removes it.
+    fn collect_pickups(&mut self) {
+        let start = self.player.previous_hitbox();
+        let end = self.player.hitbox();
+        let (dx, dy) = (end.left() - start.left(), end.top() - start.top());
+        let player = &mut self.player;
+
+        self.pickups.retain(|pickup| {
+            let touched = start.overlaps_along(dx, dy, &pickup.hitbox());
+            if touched {
+                player.collect(pickup.kind());
+            }
+            !touched
+        });
+    }
+
+    /// Drops obstacles the player has fully cleared, scoring for each, and
+    /// the pickups left behind with them.
+    fn despawn_passed_obstacles(&mut self) -> usize {
+        let player_left = self.player.full_hitbox().left();
+        let mut passed = 0;
+
+        while self
+            .obstacles
+            .front()
+            .is_some_and(|obstacle| obstacle.right() as f32 <= player_left)
+        {
+            self.obstacles.pop_front();
+            self.score.add(self.player.score_multiplier());
+            self.obstacles_passed += 1;
+            passed += 1;
+        }
+
+        while self
+            .pickups
+            .front()
+            .is_some_and(|pickup| pickup.right() as f32 <= player_left)
+        {
+            self.pickups.pop_front();
+        }
+
+        passed
+    }
+
+    /// Sets the player's scroll speed for the obstacles passed and tick.
+    fn apply_scroll_speed(&mut self) {
+        let speed = self
+            .difficulty
+            .params(self.obstacles_passed, self.tick)
+            .scroll_speed;
+        self.player.set_movement_speed(speed.max(1));
+    }
+
+    /// Fills the queue until the newest obstacle lies beyond the screen.
+    ///
+    /// Each obstacle's difficulty is based on the number of obstacles
+    /// before it.
+    fn spawn_obstacles(&mut self) {
+        let horizon = self.camera_x() + self.config.display.screen_width;
+
+        loop {
+            let difficulty = self.difficulty.params(self.obstacles_spawned, self.tick);
+            let x = match self.obstacles.back() {
+                Some(last) if last.x() >= horizon => break,
+                Some(last) => last.x() + difficulty.spacing.max(last.width() + 1),
+                None => self.config.display.screen_width,
+            };
+
+            let obstacle = self.generate_obstacle(x, &difficulty);
+            if let Some(pickup) = self.roll_pickup(&obstacle) {
+                self.pickups.push_back(pickup);
+            }
+            self.obstacles.push_back(obstacle);
+            self.obstacles_spawned += 1;
+        }
+    }
+
+    /// Decides whether a pickup waits in the gap of `obstacle`, and which.
+    ///
+    /// Obstacles whose gap leaves no row open for their whole life carry
+    /// none. Nothing is drawn from the random number generator when
+    /// power-ups are disabled, so those runs keep the course they had
+    /// without them.
+    fn roll_pickup(&mut self, obstacle: &Obstacle) -> Option<Pickup> {
+        let power_ups = &self.config.power_ups;
+        if !power_ups.enabled()
+            || obstacle.steady_gap_rows().is_empty()
+            || self.rng.range(0, 100) >= power_ups.spawn_chance
+        {
+            return None;
+        }
+
+        Pickup::in_gap_of(obstacle, PickupKind::choose(&mut self.rng))
+    }
+
+    /// Creates an obstacle at `x` that a perfect player can get through.
+    ///
+    /// Random obstacles are rolled a few times; if none is reachable, the
+    /// last roll is replaced by the static gap closest to it that is. The
+    /// last roll is kept as is only if no gap at all can be reached.
+    fn generate_obstacle(&mut self, x: i32, difficulty: &DifficultyParams) -> Obstacle {
+        let curve = Arc::clone(&self.difficulty);
+        let spawn_tick = self.tick;
+        let approach = self.planner.approach(x, curve.as_ref());
+        let mut rolled = None;
+
+        for _ in 0..OBSTACLE_GENERATION_ATTEMPTS {
+            let obstacle = Obstacle::new(x, difficulty, &mut self.rng, &self.config.obstacle);
+            let planner = approach
+                .as_ref()
+                .and_then(|approach| approach.through(&obstacle, spawn_tick, curve.as_ref()));
+            if let Some(planner) = planner {
+                self.planner = planner;
+                return obstacle;
+            }
+            rolled = Some(obstacle);
+        }
+
+        let rolled = rolled
+            .unwrap_or_else(|| Obstacle::new(x, difficulty, &mut self.rng, &self.config.obstacle));
+        if let Some(approach) = &approach {
+            for obstacle in self.adjusted_obstacles(&rolled, difficulty) {
+                if let Some(planner) = approach.through(&obstacle, spawn_tick, curve.as_ref()) {
+                    self.planner = planner;
+                    return obstacle;
+                }
+            }
+        }
+
+        if let Some(planner) = self.planner.ignoring(&rolled, spawn_tick, curve.as_ref()) {
+            self.planner = planner;
+        }
+        rolled
+    }
+
+    /// Static replacements for `rolled`, from the most to the least similar.
+    ///
+    /// Gaps grow from the difficulty's gap size up to the screen height,
+    /// and for each size the centers are tried nearest to `rolled` first.
+    fn adjusted_obstacles(
+        &self,
+        rolled: &Obstacle,
+        difficulty: &DifficultyParams,
+    ) -> impl Iterator<Item = Obstacle> + use<> {
+        let config = self.config.obstacle;
+        let screen_height = self.config.display.screen_height;
+        let x = rolled.x();
+        let rolled_center = rolled.gap().center_y();
+
+        let mut centers: Vec<_> = (0..screen_height).collect();
+        centers.sort_by_key(|center| (center - rolled_center).abs());
+
+        let min_size = difficulty.gap_size.max(config.min_gap_size);
+        (min_size..=screen_height)
+            .step_by(2)
+            .flat_map(move |size| {
+                centers
+                    .clone()
+                    .into_iter()
+                    .map(move |center| ObstacleGap::new(center, size / 2))
+            })
+            .map(move |gap| Obstacle::with_gap(x, gap, &config))
+    }
+
+    /// Checks for a crash, spending the player's shield on an obstacle it
+    /// would otherwise have crashed into.
+    fn has_crashed(&mut self) -> bool {
+        if self.player.is_out_of_bounds() {
+            return true;
+        }
+        if !self.config.mode.obstacles_are_lethal() {
+            return false;
+        }
+
+        let shielded = self.player.effects().shielded_obstacle();
+        let hit = self
+            .obstacles
+            .iter()
+            .find(|obstacle| {
+                Some(obstacle.x()) != shielded && obstacle.has_collision_with(&self.player)
+            })
+            .map(Obstacle::x);
+
+        hit.is_some_and(|x| !self.player.absorb_collision(x))
+    }
+}
+
+impl fmt::Debug for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Simulation")
+            .field("player", &self.player)
+            .field("obstacles", &self.obstacles)
+            .field("pickups", &self.pickups)
+            .field("score", &self.score)
+            .field("difficulty", &self.difficulty)
+            .field("seed", &self.seed)
+            .field("tick", &self.tick)
+            .field("game_over", &self.game_over)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 64-bit FNV-1a, whose output, unlike the standard library's hashers,
+/// is fixed across builds and platforms.
+struct StateHasher(u64);
+
+impl StateHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Draws a new seed from the operating system's entropy source.
+pub fn random_seed() -> u64 {
+    RandomNumberGenerator::new().next_u64()
+}
@@ -0,0 +1,60 @@
+use crate::game::transitions::render_world;
+use crate::net::SpectatorClient;
+use crate::render::Renderer;
+use bracket_lib::prelude::*;
+
+const SPECTATOR_STATUS_Y: i32 = 0;
+const SPECTATOR_SCORE_Y: i32 = 1;
+
+/// A window following the broadcast of another game.
+///
+/// Every frame applies the lines that have arrived and draws the latest
+/// state with the game's own renderer. Escape or Q closes the window.
+#[derive(Debug)]
+pub struct SpectatorMode {
+    client: SpectatorClient,
+}
+
+impl SpectatorMode {
+    pub fn new(client: SpectatorClient) -> Self {
+        Self { client }
+    }
+
+    pub fn client(&self) -> &SpectatorClient {
+        &self.client
+    }
+
+    /// Draws the latest state of the broadcast with its status lines.
+    pub fn render(&self, ctx: &mut dyn Renderer) {
+        let client = &self.client;
+        render_world(client.player(), client.obstacles(), client.pickups(), ctx);
+
+        let status = if !client.is_connected() {
+            "Broadcast ended. Q to quit.".to_string()
+        } else if client.is_game_over() {
+            format!(
+                "Spectating {}: crashed, waiting for the next run",
+                client.peer_addr()
+            )
+        } else {
+            format!("Spectating {}. Q to quit.", client.peer_addr())
+        };
+        ctx.print(0, SPECTATOR_STATUS_Y, &status);
+        ctx.print(
+            0,
+            SPECTATOR_SCORE_Y,
+            &format!("Score: {}  Seed: {}", client.score(), client.seed()),
+        );
+    }
+}
+
+impl GameState for SpectatorMode {
+    fn tick(&mut self, ctx: &mut BTerm) {
+        self.client.poll();
+        self.render(ctx);
+
+        if let Some(VirtualKeyCode::Escape | VirtualKeyCode::Q) = ctx.key {
+            ctx.quitting = true;
+        }
+    }
+}
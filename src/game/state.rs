@@ -1,10 +1,22 @@
-use crate::config::{FRAME_DURATION_MS, SCREEN_WIDTH};
+use crate::agent::{Agent, Observation};
+use crate::config::{
+    GameConfig, HIGH_SCORE_NAME_MAX_LEN, RESUME_COUNTDOWN_MS, RESUME_COUNTDOWN_STEPS,
+};
 use crate::environment::Obstacle;
-use crate::game::menu::{game_over_menu, main_menu};
+use crate::game::menu::{characters_menu, game_over_menu, high_scores_menu, main_menu, pause_menu};
+use crate::game::replay::{
+    Replay, ReplayPlayback, ReplayRecorder, find_best_replay, new_replay_path,
+};
 use crate::game::transitions::play_game;
-use crate::game::GameMode;
-use crate::player::Player;
+use crate::game::{
+    AttractMode, GameMode, Ghost, HighScoreEntry, HighScoreTable, Opponent, PlayMode, Seat,
+    Simulation, TickInput, TickOutcome, Versus, race_winner, random_seed,
+};
+use crate::net::{NetSession, SpectatorServer};
+use crate::player::{Player, Skin, SkinRegistry};
 use bracket_lib::prelude::*;
+use std::collections::VecDeque;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub struct Score {
@@ -24,6 +36,10 @@ impl Score {
         self.value += 1;
     }
 
+    pub fn add(&mut self, points: i32) {
+        self.value += points;
+    }
+
     pub fn reset(&mut self) {
         self.value = 0;
     }
@@ -38,21 +54,42 @@ impl Default for Score {
 #[derive(Debug)]
 pub struct GameTimer {
     accumulated_time: f32,
+    frame_duration_ms: f32,
+    paused: bool,
 }
 
 impl GameTimer {
-    pub fn new() -> Self {
+    pub fn new(frame_duration_ms: f32) -> Self {
         Self {
             accumulated_time: 0.0,
+            frame_duration_ms,
+            paused: false,
         }
     }
 
+    /// Adds elapsed time; ignored while the timer is paused.
     pub fn update(&mut self, delta_time: f32) {
-        self.accumulated_time += delta_time;
+        if !self.paused {
+            self.accumulated_time += delta_time;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Freezes or unfreezes time accumulation, keeping the time gathered so far.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Changes the time between ticks, keeping the time gathered so far.
+    pub fn set_frame_duration(&mut self, frame_duration_ms: f32) {
+        self.frame_duration_ms = frame_duration_ms;
     }
 
     pub fn should_tick(&mut self) -> bool {
-        if self.accumulated_time >= FRAME_DURATION_MS {
+        if self.accumulated_time >= self.frame_duration_ms {
             self.accumulated_time = 0.0;
             true
         } else {
@@ -62,49 +99,109 @@ impl GameTimer {
 
     pub fn reset(&mut self) {
         self.accumulated_time = 0.0;
+        self.paused = false;
     }
 }
 
 impl Default for GameTimer {
     fn default() -> Self {
-        Self::new()
+        Self::new(GameConfig::default().display.frame_duration_ms)
     }
 }
 
 #[derive(Debug)]
 pub struct GameState {
-    player: Player,
+    config: GameConfig,
+    simulation: Simulation,
     timer: GameTimer,
-    obstacle: Obstacle,
     mode: GameMode,
-    score: Score,
+    pending_input: TickInput,
+    resume_countdown_ms: f32,
+    fixed_seed: Option<u64>,
+    recorder: Option<ReplayRecorder>,
+    playback: Option<ReplayPlayback>,
+    replay_dir: Option<PathBuf>,
+    last_replay: Option<Replay>,
+    replay_message: Option<String>,
+    high_scores: HighScoreTable,
+    high_score_path: Option<PathBuf>,
+    high_score_load_error: Option<String>,
+    high_scores_return: GameMode,
+    name_entry: Option<String>,
+    high_score_message: Option<String>,
+    skins: SkinRegistry,
+    attract: Option<AttractMode>,
+    autopilot: Option<Box<dyn Agent>>,
+    opponent: Option<Opponent>,
+    ghost: Option<Ghost>,
+    ghost_replay: Option<Replay>,
+    versus: Option<Versus>,
+    versus_enabled: bool,
+    network: Option<NetSession>,
+    spectators: Option<SpectatorServer>,
 }
 
 impl GameState {
     pub fn new() -> Self {
+        Self::with_config(GameConfig::default(), None)
+    }
+
+    /// Creates a game state that plays by `config`.
+    ///
+    /// If `seed` is given, every run replays the course of that seed;
+    /// otherwise each run draws a fresh one.
+    pub fn with_config(config: GameConfig, seed: Option<u64>) -> Self {
         Self {
-            player: Player::new(5, 25),
-            timer: GameTimer::new(),
-            obstacle: Obstacle::new(SCREEN_WIDTH, 0),
+            simulation: Simulation::with_config(config.clone(), seed.unwrap_or_else(random_seed)),
+            timer: GameTimer::new(config.display.frame_duration_ms),
+            config,
             mode: GameMode::default(),
-            score: Score::new(),
+            pending_input: TickInput::default(),
+            resume_countdown_ms: 0.0,
+            fixed_seed: seed,
+            recorder: None,
+            playback: None,
+            replay_dir: None,
+            last_replay: None,
+            replay_message: None,
+            high_scores: HighScoreTable::new(),
+            high_score_path: None,
+            high_score_load_error: None,
+            high_scores_return: GameMode::Menu,
+            name_entry: None,
+            high_score_message: None,
+            skins: SkinRegistry::builtin(),
+            attract: None,
+            autopilot: None,
+            opponent: None,
+            ghost: None,
+            ghost_replay: None,
+            versus: None,
+            versus_enabled: false,
+            network: None,
+            spectators: None,
         }
     }
 
-    pub fn player(&self) -> &Player {
-        &self.player
+    /// Creates a game state whose every run replays the course of `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_config(GameConfig::default(), Some(seed))
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
     }
 
-    pub fn player_mut(&mut self) -> &mut Player {
-        &mut self.player
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
     }
 
-    pub fn obstacle(&self) -> &Obstacle {
-        &self.obstacle
+    pub fn player(&self) -> &Player {
+        self.simulation.player()
     }
 
-    pub fn obstacle_mut(&mut self) -> &mut Obstacle {
-        &mut self.obstacle
+    pub fn obstacles(&self) -> &VecDeque<Obstacle> {
+        self.simulation.obstacles()
     }
 
     pub fn mode(&self) -> GameMode {
@@ -116,28 +213,567 @@ impl GameState {
     }
 
     pub fn score(&self) -> i32 {
-        self.score.get()
+        self.simulation.score()
+    }
+
+    /// Switches the next run to the following difficulty preset.
+    pub fn cycle_difficulty(&mut self) {
+        self.config.difficulty.preset = self.config.difficulty.preset.next();
+        self.attract = None;
+    }
+
+    /// Character the player flies as.
+    pub fn skin(&self) -> &Skin {
+        &self.config.skin
+    }
+
+    /// Replaces the characters offered in the characters menu.
+    pub fn set_skins(&mut self, skins: SkinRegistry) {
+        self.skins = skins;
     }
 
-    pub fn increment_score(&mut self) {
-        self.score.increment();
+    pub fn skins(&self) -> &SkinRegistry {
+        &self.skins
     }
 
+    /// Switches the next run to the character at `index` of the registry.
+    pub fn select_skin(&mut self, index: usize) {
+        if let Some(skin) = self.skins.skins().get(index) {
+            self.config.skin = skin.clone();
+            self.attract = None;
+        }
+    }
+
+    /// The demo run shown behind the main menu, if one is playing.
+    pub fn attract(&self) -> Option<&AttractMode> {
+        self.attract.as_ref()
+    }
+
+    /// Advances the demo run, starting one if none is playing.
+    pub fn update_attract(&mut self, delta_time: f32) {
+        self.attract
+            .get_or_insert_with(|| AttractMode::new(&self.config))
+            .update(delta_time);
+    }
+
+    /// Rule set of the current run.
+    pub fn play_mode(&self) -> PlayMode {
+        self.simulation.config().mode
+    }
+
+    /// Seed of the current run's obstacle course.
+    pub fn seed(&self) -> u64 {
+        self.simulation.seed()
+    }
+
+    /// Factor the time between ticks is currently stretched by.
+    ///
+    /// Every player stepped on this machine shares one clock, so slow
+    /// motion is a shared effect: the tick rate follows the slowest time
+    /// scale of player one, player two and the opponent, among those
+    /// still flying.
+    pub fn time_scale(&self) -> f32 {
+        let second = self.versus.as_ref().map(Versus::simulation);
+        let opponent = self.opponent.as_ref().map(Opponent::simulation);
+
+        std::iter::once(&self.simulation)
+            .chain(second)
+            .chain(opponent)
+            .filter(|simulation| !simulation.is_game_over())
+            .map(|simulation| simulation.player().time_scale())
+            .fold(1.0, f32::max)
+    }
+
+    /// Adds elapsed time and reports whether the run is due a tick.
+    ///
+    /// Ticks are spaced by the configured frame duration, stretched by
+    /// [`GameState::time_scale`].
     pub fn timer_should_tick(&mut self, delta_time: f32) -> bool {
+        let time_scale = self.time_scale();
+        self.timer
+            .set_frame_duration(self.config.display.frame_duration_ms * time_scale);
         self.timer.update(delta_time);
         self.timer.should_tick()
     }
 
-    pub fn reset_game(&mut self) {
-        self.player = Player::new(5, SCREEN_WIDTH / 2);
+    /// Lets `agent` fly the player instead of the keyboard.
+    ///
+    /// Autopilot runs are still recorded but never enter the high scores.
+    pub fn set_autopilot(&mut self, agent: Box<dyn Agent>) {
+        self.autopilot = Some(agent);
+    }
+
+    /// The agent flying the player, if the autopilot is on.
+    pub fn autopilot(&self) -> Option<&dyn Agent> {
+        self.autopilot.as_deref()
+    }
+
+    /// Lets `agent` race the player on the same course in every run.
+    pub fn set_opponent(&mut self, agent: Box<dyn Agent>) {
+        self.opponent = Some(Opponent::new(agent, self.config.clone(), self.seed()));
+    }
+
+    /// The bot racing the player, if there is one.
+    pub fn opponent(&self) -> Option<&Opponent> {
+        self.opponent.as_ref()
+    }
+
+    /// Races `replay` as a ghost whenever a run is played on its course,
+    /// unless a better replay of that course has been saved.
+    pub fn set_ghost(&mut self, replay: Replay) {
+        self.ghost_replay = Some(replay);
+    }
+
+    /// The best earlier run of the current course, if one was recorded.
+    pub fn ghost(&self) -> Option<&Ghost> {
+        self.ghost.as_ref()
+    }
+
+    /// Starts a local versus run, with a second player on the same course.
+    ///
+    /// Versus runs are not recorded and never enter the high scores. They
+    /// continue through restarts until [`GameState::end_versus`].
+    pub fn start_versus(&mut self) {
+        self.versus_enabled = true;
+        self.reset_game();
+    }
+
+    /// Makes the next run a single-player one again.
+    pub fn end_versus(&mut self) {
+        self.versus_enabled = false;
+        self.versus = None;
+    }
+
+    /// The second player, if this is a versus run.
+    pub fn versus(&self) -> Option<&Versus> {
+        self.versus.as_ref()
+    }
+
+    /// Races the rival of `session` on the course agreed in its handshake.
+    ///
+    /// Network runs never enter the high scores and cannot be restarted;
+    /// the race ends when the player quits to the menu. Settings that only
+    /// concern this machine are kept.
+    pub fn start_network(&mut self, session: NetSession) {
+        let mut config = session.config().clone();
+        config.apply_local(&self.config);
+        self.config = config;
+        self.fixed_seed = Some(session.seed());
+        self.versus_enabled = false;
+        self.versus = None;
+        let seed = session.seed();
+        self.network = Some(session);
+        self.reset_game_with_seed(seed);
+    }
+
+    /// The link to the rival, if this is a network race.
+    pub fn network(&self) -> Option<&NetSession> {
+        self.network.as_ref()
+    }
+
+    /// Applies the rival's latest inputs and keeps the connection alive.
+    pub fn poll_network(&mut self) {
+        if let Some(network) = &mut self.network {
+            network.poll();
+        }
+    }
+
+    /// Returns `false` during a network race, which is played only once
+    /// and ends on its result screen.
+    pub fn can_restart(&self) -> bool {
+        self.network.is_none()
+    }
+
+    /// The run of the second player: player two's in a versus run, or the
+    /// rival's in a network race.
+    pub fn second_player(&self) -> Option<&Simulation> {
+        match (&self.versus, &self.network) {
+            (Some(versus), _) => Some(versus.simulation()),
+            (None, Some(network)) => Some(network.remote()),
+            (None, None) => None,
+        }
+    }
+
+    /// The winner of a two-player run once both players have crashed, or
+    /// `None` for a draw or a single-player run.
+    ///
+    /// In a network race [`Seat::One`] is the local player.
+    pub fn versus_winner(&self) -> Option<Seat> {
+        self.second_player()
+            .and_then(|second| race_winner(&self.simulation, second))
+    }
+
+    /// The run the camera follows: player one's, unless player one has
+    /// crashed while the second player is still flying.
+    pub fn camera_simulation(&self) -> &Simulation {
+        match self.second_player() {
+            Some(second) if self.simulation.is_game_over() && !second.is_game_over() => second,
+            _ => &self.simulation,
+        }
+    }
+
+    /// Broadcasts every run, tick by tick, through `server`.
+    pub fn set_spectators(&mut self, server: SpectatorServer) {
+        self.spectators = Some(server);
+    }
+
+    /// The broadcast of this game's runs, if there is one.
+    pub fn spectators(&self) -> Option<&SpectatorServer> {
+        self.spectators.as_ref()
+    }
+
+    /// Queues a flap for player one on the next simulation tick.
+    ///
+    /// Ignored while a replay is being played back, the autopilot is
+    /// flying or the resume countdown is running.
+    pub fn queue_flap(&mut self) {
+        self.queue_flap_for(Seat::One);
+    }
+
+    /// Queues a flap for the player in `seat` on the next simulation tick.
+    ///
+    /// Flaps for player two are ignored outside versus runs.
+    pub fn queue_flap_for(&mut self, seat: Seat) {
+        if self.playback.is_some() || self.is_counting_down() {
+            return;
+        }
+
+        match (seat, &mut self.versus) {
+            (Seat::One, _) if self.autopilot.is_none() => self.pending_input.flap = true,
+            (Seat::Two, Some(versus)) => versus.queue_flap(),
+            _ => {}
+        }
+    }
+
+    /// Freezes the current run and opens the pause menu.
+    pub fn pause(&mut self) {
+        if self.mode == GameMode::Playing {
+            self.mode = GameMode::Paused;
+            self.timer.set_paused(true);
+            self.pending_input = TickInput::default();
+            if let Some(versus) = &mut self.versus {
+                versus.clear_input();
+            }
+            self.resume_countdown_ms = 0.0;
+        }
+    }
+
+    /// Leaves the pause menu; physics restart after a short countdown.
+    pub fn resume(&mut self) {
+        if self.mode == GameMode::Paused {
+            self.mode = GameMode::Playing;
+            self.resume_countdown_ms = RESUME_COUNTDOWN_MS;
+        }
+    }
+
+    /// Abandons the current run without recording it and returns to the main menu.
+    ///
+    /// A network race is left for good.
+    pub fn quit_to_menu(&mut self) {
+        self.recorder = None;
+        self.network = None;
+        self.playback = None;
         self.timer.reset();
-        self.obstacle = Obstacle::new(SCREEN_WIDTH, 0);
-        self.mode = GameMode::Playing;
-        self.score.reset();
+        self.resume_countdown_ms = 0.0;
+        self.mode = GameMode::Menu;
+    }
+
+    pub fn is_counting_down(&self) -> bool {
+        self.resume_countdown_ms > 0.0
+    }
+
+    /// Number shown by the resume countdown, or `None` when it is not running.
+    pub fn countdown_value(&self) -> Option<i32> {
+        self.is_counting_down().then(|| {
+            let step_ms = RESUME_COUNTDOWN_MS / RESUME_COUNTDOWN_STEPS as f32;
+            (self.resume_countdown_ms / step_ms).ceil() as i32
+        })
+    }
+
+    /// Advances the resume countdown, unfreezing the timer once it ends.
+    pub fn update_countdown(&mut self, delta_time: f32) {
+        if !self.is_counting_down() {
+            return;
+        }
+
+        self.resume_countdown_ms -= delta_time;
+        if !self.is_counting_down() {
+            self.resume_countdown_ms = 0.0;
+            self.timer.set_paused(false);
+        }
+    }
+
+    /// Advances the simulation with the input gathered since the last tick.
+    ///
+    /// During replay playback the recorded input is used instead, and
+    /// [`TickOutcome::GameOver`] is also returned once the replay runs out.
+    /// The autopilot, if on, decides the input itself, and the opponent
+    /// and ghost, if any, play their own ticks alongside. In a network
+    /// race each tick the player flies is sent to the rival, and the run
+    /// only ends once the rival has crashed or left too. Spectators, if
+    /// any, are sent the state of the run after every tick.
+    pub fn step(&mut self) -> TickOutcome {
+        let tick = self.simulation.tick();
+        let input = match (&mut self.playback, &mut self.autopilot) {
+            (Some(playback), _) => playback.next_input(tick),
+            (None, Some(agent)) => agent.act(&Observation::of(&self.simulation)),
+            (None, None) => std::mem::take(&mut self.pending_input),
+        };
+
+        if let Some(opponent) = &mut self.opponent {
+            opponent.step();
+        }
+        if let Some(ghost) = &mut self.ghost {
+            ghost.step();
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(tick, input);
+        }
+
+        let was_over = self.simulation.is_game_over();
+        let mut outcome = self.simulation.step(input);
+
+        if let Some(network) = &mut self.network {
+            if !was_over {
+                network.send_tick(tick, input, &self.simulation);
+            }
+            if outcome == TickOutcome::GameOver && network.is_rival_racing() {
+                outcome = TickOutcome::Running;
+            }
+        }
+
+        if let Some(versus) = &mut self.versus {
+            versus.step();
+            if outcome == TickOutcome::GameOver && !versus.simulation().is_game_over() {
+                outcome = TickOutcome::Running;
+            }
+        }
+
+        if let Some(spectators) = &mut self.spectators {
+            spectators.publish(&self.simulation);
+        }
+
+        let replay_exhausted = self
+            .playback
+            .as_ref()
+            .is_some_and(|playback| playback.is_finished(self.simulation.tick()));
+        if replay_exhausted {
+            outcome = TickOutcome::GameOver;
+        }
+
+        if outcome == TickOutcome::GameOver {
+            self.finish_run();
+        }
+
+        outcome
+    }
+
+    /// Sets the directory finished runs are saved to, or disables saving.
+    pub fn set_replay_dir(&mut self, replay_dir: Option<PathBuf>) {
+        self.replay_dir = replay_dir;
+    }
+
+    /// Replay of the most recently finished recorded run.
+    pub fn last_replay(&self) -> Option<&Replay> {
+        self.last_replay.as_ref()
+    }
+
+    /// Status line describing where the last replay was saved.
+    pub fn replay_message(&self) -> Option<&str> {
+        self.replay_message.as_deref()
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Starts playing `replay` back through the regular tick pipeline.
+    pub fn start_replay(&mut self, replay: Replay) {
+        self.reset_game_with_seed(replay.seed);
+        self.simulation = Simulation::with_config(replay.config.clone(), replay.seed);
+        self.recorder = None;
+        self.ghost = None;
+        self.playback = Some(ReplayPlayback::new(replay));
+    }
+
+    /// Loads the high-score table from `path` and saves new entries there.
+    ///
+    /// If the file cannot be read or is malformed, the table starts empty,
+    /// the error is shown on the High Scores screen and new entries are
+    /// kept in memory only, leaving the file as it was.
+    pub fn load_high_scores(&mut self, path: PathBuf) {
+        match HighScoreTable::load(&path) {
+            Ok(table) => {
+                self.high_scores = table;
+                self.high_score_path = Some(path);
+                self.high_score_load_error = None;
+            }
+            Err(e) => {
+                self.high_scores = HighScoreTable::new();
+                self.high_score_path = None;
+                let error = e.to_string();
+                self.high_score_load_error = error.lines().next().map(str::to_string);
+            }
+        }
+    }
+
+    pub fn high_scores(&self) -> &HighScoreTable {
+        &self.high_scores
     }
 
-    pub fn spawn_new_obstacle(&mut self) {
-        self.obstacle = Obstacle::new(self.player.x() + SCREEN_WIDTH, self.score.get());
+    /// Why the saved high scores could not be loaded, if they could not.
+    pub fn high_score_load_error(&self) -> Option<&str> {
+        self.high_score_load_error.as_deref()
+    }
+
+    /// Opens the High Scores screen, which returns to the current screen
+    /// when closed.
+    pub fn show_high_scores(&mut self) {
+        self.high_scores_return = self.mode;
+        self.mode = GameMode::HighScores;
+    }
+
+    /// Leaves the High Scores screen for the screen it was opened from.
+    pub fn close_high_scores(&mut self) {
+        self.mode = self.high_scores_return;
+    }
+
+    /// Name typed so far, while the player is entering a new high score.
+    pub fn name_entry(&self) -> Option<&str> {
+        self.name_entry.as_deref()
+    }
+
+    /// Status line describing whether the last high score was saved.
+    pub fn high_score_message(&self) -> Option<&str> {
+        self.high_score_message.as_deref()
+    }
+
+    /// Appends a character to the name being entered.
+    pub fn push_name_char(&mut self, c: char) {
+        if let Some(name) = &mut self.name_entry
+            && name.chars().count() < HIGH_SCORE_NAME_MAX_LEN
+        {
+            name.push(c);
+        }
+    }
+
+    /// Removes the last character of the name being entered.
+    pub fn pop_name_char(&mut self) {
+        if let Some(name) = &mut self.name_entry {
+            name.pop();
+        }
+    }
+
+    /// Records the finished run in the high-score table under the entered name.
+    pub fn submit_high_score(&mut self) {
+        let Some(name) = self.name_entry.take() else {
+            return;
+        };
+        let name = match name.trim() {
+            "" => "Cheems".to_string(),
+            trimmed => trimmed.to_string(),
+        };
+
+        let entry = HighScoreEntry::new(name, self.score(), self.seed(), self.play_mode());
+        let Some(rank) = self.high_scores.insert(entry) else {
+            return;
+        };
+
+        self.high_score_message = Some(match &self.high_score_path {
+            Some(path) => match self.high_scores.save(path) {
+                Ok(()) => format!("Ranked #{} in the high scores!", rank + 1),
+                Err(e) => format!("High score not saved: {}", e),
+            },
+            None => format!("Ranked #{} in the high scores!", rank + 1),
+        });
+    }
+
+    fn finish_run(&mut self) {
+        self.replay_message = None;
+        self.high_score_message = None;
+
+        if !self.is_replaying()
+            && self.autopilot.is_none()
+            && self.versus.is_none()
+            && self.network.is_none()
+            && self.play_mode().is_ranked()
+            && self.high_scores.qualifies(self.score())
+        {
+            self.name_entry = Some(String::new());
+        }
+
+        if let Some(recorder) = self.recorder.take() {
+            self.save_replay(recorder);
+        }
+    }
+
+    fn save_replay(&mut self, recorder: ReplayRecorder) {
+        let replay = recorder.finish(&self.simulation);
+
+        if let Some(dir) = &self.replay_dir {
+            let path = new_replay_path(dir, replay.seed);
+            self.replay_message = Some(match replay.save(&path) {
+                Ok(()) => format!("Replay saved to {}", path.display()),
+                Err(e) => format!("Replay not saved: {}", e),
+            });
+        }
+
+        self.last_replay = Some(replay);
+    }
+
+    /// The highest-scoring recorded run of the course `seed` generates
+    /// under the current configuration.
+    fn best_replay_of(&self, seed: u64) -> Option<Replay> {
+        let saved = self
+            .replay_dir
+            .as_ref()
+            .and_then(|dir| find_best_replay(dir, seed, &self.config));
+
+        [self.ghost_replay.clone(), self.last_replay.clone(), saved]
+            .into_iter()
+            .flatten()
+            .filter(|replay| replay.seed == seed && replay.config == self.config)
+            .max_by_key(|replay| replay.final_score)
+    }
+
+    pub fn reset_game(&mut self) {
+        let seed = self.fixed_seed.unwrap_or_else(random_seed);
+        self.reset_game_with_seed(seed);
+    }
+
+    /// Starts a new run on the course generated by `seed`.
+    pub fn reset_game_with_seed(&mut self, seed: u64) {
+        self.simulation = Simulation::with_config(self.config.clone(), seed);
+        self.timer.reset();
+        self.pending_input = TickInput::default();
+        self.resume_countdown_ms = 0.0;
+        self.versus = self
+            .versus_enabled
+            .then(|| Versus::new(self.config.clone(), seed));
+        self.recorder = self
+            .versus
+            .is_none()
+            .then(|| ReplayRecorder::new(seed, self.config.clone()));
+        self.playback = None;
+        self.replay_message = None;
+        self.name_entry = None;
+        self.high_score_message = None;
+        self.attract = None;
+        if let Some(agent) = &mut self.autopilot {
+            agent.reset();
+        }
+        if let Some(opponent) = &mut self.opponent {
+            opponent.restart(self.config.clone(), seed);
+        }
+        self.ghost = match (&self.versus, &self.network) {
+            (None, None) => self.best_replay_of(seed).map(Ghost::new),
+            _ => None,
+        };
+        if let Some(spectators) = &mut self.spectators {
+            spectators.publish(&self.simulation);
+        }
+        self.mode = GameMode::Playing;
     }
 }
 
@@ -149,10 +785,17 @@ impl Default for GameState {
 
 impl bracket_lib::prelude::GameState for GameState {
     fn tick(&mut self, ctx: &mut BTerm) {
+        if let Some(spectators) = &mut self.spectators {
+            spectators.poll(&self.simulation);
+        }
+
         match self.mode {
             GameMode::Menu => main_menu(self, ctx),
             GameMode::GameOver => game_over_menu(self, ctx),
+            GameMode::HighScores => high_scores_menu(self, ctx),
+            GameMode::Characters => characters_menu(self, ctx),
             GameMode::Playing => play_game(self, ctx),
+            GameMode::Paused => pause_menu(self, ctx),
         }
     }
 }
@@ -1,56 +1,317 @@
-use crate::game::{GameMode, GameState};
+use crate::config::GHOST_OPACITY;
+use crate::environment::{Obstacle, Pickup, PickupKind};
+use crate::game::{GameMode, GameState, Seat, Simulation, TickOutcome};
+use crate::net::{NetSession, PeerStatus};
+use crate::player::Player;
+use crate::render::{Renderer, SPRITE_LAYER, TEXT_LAYER};
 use bracket_lib::prelude::*;
 
 const UI_INSTRUCTIONS_Y: i32 = 0;
 const UI_SCORE_Y: i32 = 1;
+const UI_OPPONENT_Y: i32 = 2;
+const UI_GHOST_Y: i32 = 3;
+const UI_NETWORK_Y: i32 = 4;
+const UI_EFFECTS_Y: i32 = 5;
+
+const PLAYER_ONE_COLOR: (u8, u8, u8) = WHITE;
+const PLAYER_TWO_COLOR: (u8, u8, u8) = CYAN;
 
 pub fn play_game(state: &mut GameState, ctx: &mut BTerm) {
-    render_game_background(ctx);
+    handle_player_input(state, ctx);
+    state.poll_network();
 
+    state.update_countdown(ctx.frame_time_ms);
     if state.timer_should_tick(ctx.frame_time_ms) {
-        state.player_mut().update_physics();
+        update_simulation(state);
     }
 
-    handle_player_input(state, ctx);
-    render_game_elements(state, ctx);
+    render_game_frame(state, ctx);
+    render_countdown(state, ctx);
+}
+
+/// Draws the current run without advancing it.
+pub fn render_game_frame(state: &GameState, ctx: &mut dyn Renderer) {
+    render_simulation(state.camera_simulation(), ctx);
+    render_versus_players(state, ctx);
+    render_ghost(state, ctx);
+    render_opponent(state, ctx);
     render_ui(state, ctx);
+}
 
-    update_game_logic(state);
-    check_game_over_conditions(state);
+/// Draws the world of `simulation` without any interface on top.
+pub fn render_simulation(simulation: &Simulation, ctx: &mut dyn Renderer) {
+    render_world(
+        simulation.player(),
+        simulation.obstacles(),
+        simulation.pickups(),
+        ctx,
+    );
 }
 
-fn render_game_background(ctx: &mut BTerm) {
-    ctx.cls_bg(NAVY);
+/// Draws `player` among `obstacles` and `pickups`, seen from the player's
+/// column, without any interface on top.
+pub fn render_world<'a>(
+    player: &Player,
+    obstacles: impl IntoIterator<Item = &'a Obstacle>,
+    pickups: impl IntoIterator<Item = &'a Pickup>,
+    ctx: &mut dyn Renderer,
+) {
+    render_game_background(ctx);
+    render_game_elements(player, obstacles, pickups, ctx);
+}
+
+fn render_game_background(ctx: &mut dyn Renderer) {
+    ctx.cls_bg(NAVY.into());
 }
 
 fn handle_player_input(state: &mut GameState, ctx: &mut BTerm) {
-    if let Some(VirtualKeyCode::Space) = ctx.key {
-        state.player_mut().flap();
+    match ctx.key {
+        Some(VirtualKeyCode::Space | VirtualKeyCode::Up) => state.queue_flap_for(Seat::One),
+        Some(VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter) => {
+            state.queue_flap_for(Seat::Two)
+        }
+        Some(VirtualKeyCode::Escape | VirtualKeyCode::P) => state.pause(),
+        _ => {}
     }
 }
 
-fn render_game_elements(state: &mut GameState, ctx: &mut BTerm) {
-    state.player().render(ctx);
-    state.obstacle().render(ctx, state.player().x());
+fn update_simulation(state: &mut GameState) {
+    if state.step() == TickOutcome::GameOver {
+        state.set_mode(GameMode::GameOver);
+    }
 }
 
-fn render_ui(state: &GameState, ctx: &mut BTerm) {
-    ctx.print(0, UI_INSTRUCTIONS_Y, "Press SPACE to flap.");
-    ctx.print(0, UI_SCORE_Y, format!("Score: {}", state.score()));
+fn render_game_elements<'a>(
+    player: &Player,
+    obstacles: impl IntoIterator<Item = &'a Obstacle>,
+    pickups: impl IntoIterator<Item = &'a Pickup>,
+    ctx: &mut dyn Renderer,
+) {
+    let camera_x = player.x();
+
+    player.render(ctx);
+    render_ground(ctx);
+    for obstacle in obstacles {
+        obstacle.render(ctx, camera_x);
+    }
+    for pickup in pickups {
+        pickup.render(ctx, camera_x);
+    }
 }
 
-fn update_game_logic(state: &mut GameState) {
-    if state.player().x() > state.obstacle().x() {
-        state.increment_score();
-        state.spawn_new_obstacle();
+/// Redraws the sprite layer with every player of a two-player run still
+/// flying, each in their own colour.
+fn render_versus_players(state: &GameState, ctx: &mut dyn Renderer) {
+    let Some(second) = state.second_player() else {
+        return;
+    };
+
+    ctx.set_active_console(SPRITE_LAYER);
+    ctx.cls();
+    ctx.set_active_console(TEXT_LAYER);
+
+    let camera_x = state.camera_simulation().camera_x();
+    let players = [
+        (state.simulation(), PLAYER_ONE_COLOR),
+        (second, PLAYER_TWO_COLOR),
+    ];
+    for (simulation, color) in players {
+        if !simulation.is_game_over() {
+            simulation
+                .player()
+                .render_tinted(ctx, camera_x, color.into());
+        }
     }
 }
 
-fn check_game_over_conditions(state: &mut GameState) {
-    let player_out_of_bounds = state.player().is_out_of_bounds();
-    let player_hit_obstacle = state.obstacle().has_collision_with(state.player());
+fn render_ghost(state: &GameState, ctx: &mut dyn Renderer) {
+    if let Some(ghost) = state.ghost()
+        && !ghost.is_finished()
+    {
+        let tint = RGBA::from_f32(1.0, 1.0, 1.0, GHOST_OPACITY);
+        let camera_x = state.camera_simulation().camera_x();
+        ghost
+            .simulation()
+            .player()
+            .render_tinted(ctx, camera_x, tint);
+    }
+}
 
-    if player_out_of_bounds || player_hit_obstacle {
-        state.set_mode(GameMode::GameOver);
+fn render_opponent(state: &GameState, ctx: &mut dyn Renderer) {
+    if let Some(opponent) = state.opponent()
+        && !opponent.simulation().is_game_over()
+    {
+        let camera_x = state.camera_simulation().camera_x();
+        opponent
+            .simulation()
+            .player()
+            .render_tinted(ctx, camera_x, ORANGE.into());
+    }
+}
+
+fn render_ground(ctx: &mut dyn Renderer) {
+    let (screen_width, screen_height) = ctx.get_char_size();
+
+    for x in 0..screen_width as i32 {
+        ctx.set(
+            x,
+            screen_height as i32 - 1,
+            WHITE.into(),
+            WHITE.into(),
+            to_cp437('#'),
+        );
+    }
+}
+
+fn render_ui(state: &GameState, ctx: &mut dyn Renderer) {
+    match (state.versus(), state.network(), state.autopilot()) {
+        (Some(versus), _, _) => render_versus_ui(state.simulation(), versus.simulation(), ctx),
+        (None, Some(network), _) => render_network_ui(state, network, ctx),
+        (None, None, Some(agent)) => {
+            ctx.print(
+                0,
+                UI_INSTRUCTIONS_Y,
+                &format!("Autopilot: {} bot. P to pause.", agent.name()),
+            );
+            ctx.print(0, UI_SCORE_Y, &format!("Score: {}", state.score()));
+        }
+        (None, None, None) => {
+            ctx.print(
+                0,
+                UI_INSTRUCTIONS_Y,
+                &format!("Press {} to flap, P to pause.", Seat::One.key_name()),
+            );
+            ctx.print(0, UI_SCORE_Y, &format!("Score: {}", state.score()));
+        }
+    }
+
+    if let Some(opponent) = state.opponent() {
+        let simulation = opponent.simulation();
+        let status = crash_note(simulation);
+        ctx.print_color(
+            0,
+            UI_OPPONENT_Y,
+            ORANGE.into(),
+            BLACK.into(),
+            &format!(
+                "{} bot: {}{}",
+                opponent.agent().name(),
+                simulation.score(),
+                status
+            ),
+        );
+    }
+
+    if let Some(ghost) = state.ghost() {
+        let standing = ghost.lead(state.simulation());
+        ctx.print_color(
+            0,
+            UI_GHOST_Y,
+            GREY.into(),
+            BLACK.into(),
+            &format!("Ghost ({} pts): {}", ghost.replay().final_score, standing),
+        );
+    }
+
+    render_effects(state.simulation(), state.time_scale(), ctx);
+}
+
+/// Prints an icon for each power-up affecting the player of `simulation`,
+/// followed by the seconds it has left when ticks are stretched by
+/// `time_scale`.
+fn render_effects(simulation: &Simulation, time_scale: f32, ctx: &mut dyn Renderer) {
+    let player = simulation.player();
+    let frame_ms = simulation.config().display.frame_duration_ms * time_scale;
+    let mut x = 0;
+
+    for (kind, remaining) in player.effects().active() {
+        let icon = match kind {
+            PickupKind::Multiplier => format!("{}x{}", kind.glyph(), player.score_multiplier()),
+            _ => kind.glyph().to_string(),
+        };
+        let seconds = (remaining as f32 * frame_ms / 1000.0).ceil();
+        let label = format!("{} {}s", icon, seconds);
+
+        ctx.print_color(x, UI_EFFECTS_Y, kind.color().into(), BLACK.into(), &label);
+        x += label.chars().count() as i32 + 2;
+    }
+}
+
+fn render_versus_ui(first: &Simulation, second: &Simulation, ctx: &mut dyn Renderer) {
+    ctx.print(
+        0,
+        UI_INSTRUCTIONS_Y,
+        &format!(
+            "{}: {}  {}: {}  P: pause",
+            Seat::One,
+            Seat::One.key_name(),
+            Seat::Two,
+            Seat::Two.key_name()
+        ),
+    );
+
+    render_race_scores([(Seat::One.name(), first), (Seat::Two.name(), second)], ctx);
+}
+
+fn render_network_ui(state: &GameState, network: &NetSession, ctx: &mut dyn Renderer) {
+    let pilot = match state.autopilot() {
+        Some(agent) => format!("Autopilot: {} bot.", agent.name()),
+        None => format!("{} to flap.", Seat::One.key_name()),
+    };
+    ctx.print(
+        0,
+        UI_INSTRUCTIONS_Y,
+        &format!("{} P to pause. Racing {}", pilot, network.peer_addr()),
+    );
+    render_race_scores(
+        [("You", state.simulation()), ("Rival", network.remote())],
+        ctx,
+    );
+
+    let status = match network.status() {
+        PeerStatus::Connected => return,
+        PeerStatus::Disconnected => "Rival disconnected".to_string(),
+        PeerStatus::Desynced { tick } => format!("Desync at tick {}!", tick),
+    };
+    ctx.print_color(0, UI_NETWORK_Y, RED.into(), BLACK.into(), &status);
+}
+
+/// Prints the scores of both players of a two-player run side by side.
+fn render_race_scores(players: [(&str, &Simulation); 2], ctx: &mut dyn Renderer) {
+    let (screen_width, _) = ctx.get_char_size();
+    let columns = [
+        (PLAYER_ONE_COLOR, 0),
+        (PLAYER_TWO_COLOR, screen_width as i32 / 2),
+    ];
+
+    for ((label, simulation), (color, x)) in players.into_iter().zip(columns) {
+        ctx.print_color(
+            x,
+            UI_SCORE_Y,
+            color.into(),
+            BLACK.into(),
+            &format!(
+                "{}: {}{}",
+                label,
+                simulation.score(),
+                crash_note(simulation)
+            ),
+        );
+    }
+}
+
+/// Suffix marking a run whose player has crashed.
+fn crash_note(simulation: &Simulation) -> &'static str {
+    if simulation.is_game_over() {
+        " (crashed)"
+    } else {
+        ""
+    }
+}
+
+fn render_countdown(state: &GameState, ctx: &mut dyn Renderer) {
+    if let Some(value) = state.countdown_value() {
+        let (_, screen_height) = ctx.get_char_size();
+        ctx.print_centered(screen_height as i32 / 2, &value.to_string());
     }
 }
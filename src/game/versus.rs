@@ -0,0 +1,124 @@
+use crate::config::GameConfig;
+use crate::game::{Simulation, TickInput};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// One of the two players of a versus run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Seat {
+    /// Flaps with Space or Up.
+    One,
+    /// Flaps with Enter.
+    Two,
+}
+
+impl Seat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Seat::One => "Player 1",
+            Seat::Two => "Player 2",
+        }
+    }
+
+    /// Key the player flaps with.
+    ///
+    /// Player one can always flap with Up as well, and must in terminal
+    /// builds, where the space bar cannot be read.
+    pub fn key_name(&self) -> &'static str {
+        match self {
+            Seat::One if cfg!(feature = "terminal") => "UP",
+            Seat::One => "SPACE",
+            Seat::Two => "ENTER",
+        }
+    }
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The second player of a local versus run.
+///
+/// Player two flies their own [`Simulation`] of the first player's seed,
+/// so both face the same course, and is stepped in lockstep with player
+/// one. Each player keeps their own score and crashes on their own.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{Seat, Simulation, TickInput, Versus};
+///
+/// let mut one = Simulation::with_seed(4);
+/// let mut versus = Versus::new(GameConfig::default(), 4);
+///
+/// while !one.is_game_over() || !versus.simulation().is_game_over() {
+///     one.step(TickInput::IDLE);
+///     if versus.simulation().tick() % 4 == 0 {
+///         versus.queue_flap();
+///     }
+///     versus.step();
+/// }
+///
+/// assert_eq!(versus.winner(&one), Some(Seat::Two));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Versus {
+    simulation: Simulation,
+    pending_input: TickInput,
+}
+
+impl Versus {
+    /// Starts player two on the course of `seed`.
+    pub fn new(config: GameConfig, seed: u64) -> Self {
+        Self {
+            simulation: Simulation::with_config(config, seed),
+            pending_input: TickInput::default(),
+        }
+    }
+
+    /// Player two's run.
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    /// Queues a flap for player two's next tick.
+    pub fn queue_flap(&mut self) {
+        self.pending_input.flap = true;
+    }
+
+    /// Drops any flap queued since the last tick.
+    pub fn clear_input(&mut self) {
+        self.pending_input = TickInput::default();
+    }
+
+    /// Advances player two's run with the input gathered since the last tick.
+    pub fn step(&mut self) {
+        let input = std::mem::take(&mut self.pending_input);
+        self.simulation.step(input);
+    }
+
+    /// The winner against player one's run `first`, or `None` for a draw.
+    pub fn winner(&self, first: &Simulation) -> Option<Seat> {
+        race_winner(first, &self.simulation)
+    }
+}
+
+/// The winner of a race between the runs `first` and `second`, or `None`
+/// for a draw.
+///
+/// The higher score wins; equal scores go to whoever flew further.
+pub fn race_winner(first: &Simulation, second: &Simulation) -> Option<Seat> {
+    let ordering = first
+        .score()
+        .cmp(&second.score())
+        .then(first.player().x().cmp(&second.player().x()));
+
+    match ordering {
+        Ordering::Greater => Some(Seat::One),
+        Ordering::Less => Some(Seat::Two),
+        Ordering::Equal => None,
+    }
+}
@@ -0,0 +1,196 @@
+use crate::agent::{Features, Observation};
+use crate::config::GameConfig;
+use crate::error::GameResult;
+use crate::game::{Simulation, TickInput, TickOutcome};
+use crate::gym::RewardConfig;
+
+/// Details of a step that are not part of the reward signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepInfo {
+    /// Ticks simulated in the episode so far.
+    pub tick: u64,
+    /// Points earned in the episode so far.
+    pub score: i32,
+    /// Obstacles cleared on this step.
+    pub passed: i32,
+    /// Whether the episode ended by crashing.
+    pub crashed: bool,
+    /// Whether the episode was cut short by the tick limit.
+    pub truncated: bool,
+}
+
+/// A gym-style training environment around a headless [`Simulation`].
+///
+/// Episodes start with [`Env::reset`] and advance one tick per
+/// [`Env::step`], which returns the next observation as [`Features`], the
+/// reward, whether the episode is done, and a [`StepInfo`]. No run exists
+/// until the first reset, so creating an environment is cheap.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::TickInput;
+/// use flappycheems::gym::Env;
+///
+/// let mut env = Env::new(GameConfig::default())?;
+/// let mut observation = env.reset(42);
+/// let mut total = 0.0;
+///
+/// loop {
+///     let flap = observation[1] > 0.5;
+///     let (next, reward, done, _info) = env.step(TickInput { flap });
+///     total += reward;
+///     observation = next;
+///     if done {
+///         break;
+///     }
+/// }
+///
+/// assert!(env.simulation().is_game_over());
+/// assert!(total.is_finite());
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Env {
+    config: GameConfig,
+    reward: RewardConfig,
+    max_ticks: Option<u64>,
+    simulation: Option<Simulation>,
+}
+
+impl Env {
+    /// Creates an environment that plays by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config` describes an unplayable game.
+    pub fn new(config: GameConfig) -> GameResult<Self> {
+        config.validate()?;
+
+        Ok(Self {
+            simulation: None,
+            config,
+            reward: RewardConfig::default(),
+            max_ticks: None,
+        })
+    }
+
+    /// Uses `reward` to score steps.
+    pub fn with_reward(mut self, reward: RewardConfig) -> Self {
+        self.reward = reward;
+        self
+    }
+
+    /// Ends episodes after `max_ticks` ticks even if the player is alive.
+    pub fn with_max_ticks(mut self, max_ticks: u64) -> Self {
+        self.max_ticks = Some(max_ticks);
+        self
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn reward(&self) -> &RewardConfig {
+        &self.reward
+    }
+
+    /// The run of the current episode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no episode has been started with [`Env::reset`].
+    pub fn simulation(&self) -> &Simulation {
+        self.simulation
+            .as_ref()
+            .expect("Env::reset must start an episode first")
+    }
+
+    /// The current state as a structured observation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no episode has been started with [`Env::reset`].
+    pub fn observation(&self) -> Observation {
+        Observation::of(self.simulation())
+    }
+
+    /// Whether the current episode has ended, or none has started.
+    pub fn is_done(&self) -> bool {
+        self.simulation
+            .as_ref()
+            .is_none_or(|simulation| simulation.is_game_over())
+            || self.is_truncated()
+    }
+
+    /// Starts a new episode on the course of `seed`.
+    pub fn reset(&mut self, seed: u64) -> Features {
+        self.simulation = Some(Simulation::with_config(self.config.clone(), seed));
+        self.observation().features()
+    }
+
+    /// Applies `action` for one tick.
+    ///
+    /// Returns the next observation, the reward for the step, whether the
+    /// episode is done and details of the step. Once an episode is done,
+    /// further steps change nothing and earn no reward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no episode has been started with [`Env::reset`].
+    pub fn step(&mut self, action: TickInput) -> (Features, f32, bool, StepInfo) {
+        if self.is_done() {
+            let info = self.info(0, false);
+            return (self.observation().features(), 0.0, true, info);
+        }
+
+        let simulation = self
+            .simulation
+            .as_mut()
+            .expect("Env::reset must start an episode first");
+        let passed_before = simulation.obstacles_passed();
+        let outcome = simulation.step(action);
+        let passed = simulation.obstacles_passed() - passed_before;
+        let crashed = outcome == TickOutcome::GameOver;
+
+        let observation = self.observation();
+        let reward = self.reward_for(&observation, passed, crashed);
+        let info = self.info(passed, crashed);
+
+        (observation.features(), reward, self.is_done(), info)
+    }
+
+    fn reward_for(&self, observation: &Observation, passed: i32, crashed: bool) -> f32 {
+        let reward = &self.reward;
+        if crashed {
+            return reward.crash + reward.obstacle_passed * passed as f32;
+        }
+
+        let gap_offset = observation.next_gaps().next().map_or(0.0, |gap| {
+            (observation.player_y() - gap.center_y() as f32).abs()
+                / observation.screen_height.max(1) as f32
+        });
+
+        reward.survival + reward.obstacle_passed * passed as f32 - reward.gap_distance * gap_offset
+    }
+
+    fn info(&self, passed: i32, crashed: bool) -> StepInfo {
+        let simulation = self.simulation();
+        StepInfo {
+            tick: simulation.tick(),
+            score: simulation.score(),
+            passed,
+            crashed,
+            truncated: !simulation.is_game_over() && self.is_truncated(),
+        }
+    }
+
+    fn is_truncated(&self) -> bool {
+        self.max_ticks.is_some_and(|max_ticks| {
+            self.simulation
+                .as_ref()
+                .is_some_and(|simulation| simulation.tick() >= max_ticks)
+        })
+    }
+}
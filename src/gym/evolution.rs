@@ -0,0 +1,282 @@
+use crate::agent::Genome;
+use crate::config::{
+    GameConfig, TRAIN_ELITE, TRAIN_EPISODES, TRAIN_HIDDEN_NEURONS, TRAIN_MAX_TICKS,
+    TRAIN_MUTATION_RATE, TRAIN_MUTATION_STRENGTH, TRAIN_POPULATION, TRAIN_TOURNAMENT_SIZE,
+};
+use crate::error::GameResult;
+use crate::game::TickInput;
+use crate::gym::{RewardConfig, VecEnv};
+use bracket_lib::prelude::RandomNumberGenerator;
+use std::fmt;
+
+/// Settings of a neuroevolution run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvolutionConfig {
+    /// Controllers evolved in each generation.
+    pub population: usize,
+    /// Hidden neurons in each controller.
+    pub hidden: usize,
+    /// Best controllers copied unchanged into the next generation.
+    pub elite: usize,
+    /// Controllers compared when picking each parent.
+    pub tournament_size: usize,
+    /// Chance of each weight being nudged when a child is mutated.
+    pub mutation_rate: f32,
+    /// Largest nudge a mutation applies to a weight.
+    pub mutation_strength: f32,
+    /// Courses every controller plays per generation.
+    pub episodes: usize,
+    /// Tick limit for each episode.
+    pub max_ticks: u64,
+    /// Seed for the initial weights, breeding and course seeds.
+    pub seed: u64,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            population: TRAIN_POPULATION,
+            hidden: TRAIN_HIDDEN_NEURONS,
+            elite: TRAIN_ELITE,
+            tournament_size: TRAIN_TOURNAMENT_SIZE,
+            mutation_rate: TRAIN_MUTATION_RATE,
+            mutation_strength: TRAIN_MUTATION_STRENGTH,
+            episodes: TRAIN_EPISODES,
+            max_ticks: TRAIN_MAX_TICKS,
+            seed: 0,
+        }
+    }
+}
+
+/// Fitness statistics of one evaluated generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationReport {
+    /// Generation number, starting at one.
+    pub generation: u32,
+    /// Fitness of the generation's best controller.
+    pub best_fitness: f32,
+    /// Mean fitness across the generation.
+    pub mean_fitness: f32,
+    /// Fitness of the generation's worst controller.
+    pub worst_fitness: f32,
+    /// Highest score any controller reached in a single episode.
+    pub best_score: i32,
+    /// Whether the best controller beat every earlier generation.
+    pub improved: bool,
+}
+
+/// Evolves neural-network controllers over headless runs.
+///
+/// Each call to [`Evolution::evolve`] plays every genome of the current
+/// population on the same freshly drawn courses through a [`VecEnv`],
+/// kept for the whole evolution and reset on each generation's seeds,
+/// scores it by its mean episode reward, and breeds the next population
+/// from the fittest genomes by tournament selection, uniform crossover and
+/// mutation.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::gym::{Evolution, EvolutionConfig};
+///
+/// let settings = EvolutionConfig {
+///     population: 6,
+///     episodes: 1,
+///     max_ticks: 100,
+///     ..EvolutionConfig::default()
+/// };
+/// let mut evolution = Evolution::new(GameConfig::default(), settings)?;
+///
+/// let first = evolution.evolve();
+/// let second = evolution.evolve();
+/// assert_eq!((first.generation, second.generation), (1, 2));
+/// assert!(first.improved);
+/// assert!(evolution.champion().unwrap().fitness >= second.best_fitness);
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+#[derive(Clone)]
+pub struct Evolution {
+    envs: VecEnv,
+    settings: EvolutionConfig,
+    reward: RewardConfig,
+    rng: RandomNumberGenerator,
+    population: Vec<Genome>,
+    generation: u32,
+    champion: Option<Genome>,
+}
+
+impl Evolution {
+    /// Starts an evolution of random controllers playing by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config` describes an unplayable game.
+    pub fn new(config: GameConfig, settings: EvolutionConfig) -> GameResult<Self> {
+        let mut settings = settings;
+        settings.population = settings.population.max(1);
+        settings.episodes = settings.episodes.max(1);
+        settings.elite = settings.elite.min(settings.population);
+        settings.tournament_size = settings.tournament_size.max(1);
+
+        let envs = VecEnv::new(config, settings.population * settings.episodes)?
+            .with_max_ticks(settings.max_ticks);
+        let mut rng = RandomNumberGenerator::seeded(settings.seed);
+        let population = (0..settings.population)
+            .map(|_| Genome::random(settings.hidden, &mut rng))
+            .collect();
+
+        Ok(Self {
+            envs,
+            settings,
+            reward: RewardConfig::default(),
+            rng,
+            population,
+            generation: 0,
+            champion: None,
+        })
+    }
+
+    /// Scores controllers with `reward` instead of the default weights.
+    pub fn with_reward(mut self, reward: RewardConfig) -> Self {
+        self.reward = reward;
+        self.envs = self.envs.with_reward(reward);
+        self
+    }
+
+    pub fn settings(&self) -> &EvolutionConfig {
+        &self.settings
+    }
+
+    /// Number of generations evaluated so far.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Genomes waiting to be evaluated in the next generation.
+    pub fn population(&self) -> &[Genome] {
+        &self.population
+    }
+
+    /// The fittest genome of any generation so far.
+    pub fn champion(&self) -> Option<&Genome> {
+        self.champion.as_ref()
+    }
+
+    /// Evaluates the current population and breeds the next one.
+    pub fn evolve(&mut self) -> GenerationReport {
+        self.generation += 1;
+        let best_score = self.evaluate();
+
+        self.population
+            .sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+        let best = &self.population[0];
+        let improved = self
+            .champion
+            .as_ref()
+            .is_none_or(|champion| best.fitness > champion.fitness);
+        if improved {
+            self.champion = Some(best.clone());
+        }
+
+        let fitnesses = self.population.iter().map(|genome| genome.fitness);
+        let report = GenerationReport {
+            generation: self.generation,
+            best_fitness: best.fitness,
+            mean_fitness: fitnesses.clone().sum::<f32>() / self.population.len() as f32,
+            worst_fitness: fitnesses.fold(f32::INFINITY, f32::min),
+            best_score,
+            improved,
+        };
+
+        self.breed();
+        report
+    }
+
+    /// Plays every genome on this generation's courses, storing each
+    /// one's fitness, and returns the best single-episode score.
+    fn evaluate(&mut self) -> i32 {
+        let episodes = self.settings.episodes;
+        let seeds: Vec<u64> = (0..episodes).map(|_| self.rng.next_u64()).collect();
+        let env_seeds: Vec<u64> = (0..self.population.len())
+            .flat_map(|_| seeds.iter().copied())
+            .collect();
+
+        let envs = &mut self.envs;
+        let mut observations = envs.reset(&env_seeds);
+        let mut returns = vec![0.0; env_seeds.len()];
+        let mut best_score = 0;
+
+        while !envs.all_done() {
+            let actions: Vec<TickInput> = observations
+                .iter()
+                .enumerate()
+                .map(|(index, features)| TickInput {
+                    flap: self.population[index / episodes].output(features) > 0.0,
+                })
+                .collect();
+
+            let step = envs.step(&actions);
+            for (total, reward) in returns.iter_mut().zip(&step.rewards) {
+                *total += reward;
+            }
+            for info in &step.infos {
+                best_score = best_score.max(info.score);
+            }
+            observations = step.observations;
+        }
+
+        for (genome, episode_returns) in self.population.iter_mut().zip(returns.chunks(episodes)) {
+            genome.fitness = episode_returns.iter().sum::<f32>() / episodes as f32;
+            genome.generation = self.generation;
+        }
+
+        best_score
+    }
+
+    /// Replaces the evaluated population, sorted fittest first, with its
+    /// offspring.
+    fn breed(&mut self) {
+        let settings = self.settings;
+        let mut next: Vec<Genome> = self.population[..settings.elite].to_vec();
+
+        while next.len() < settings.population {
+            let mother = self.select();
+            let father = self.select();
+            let child = self.population[mother]
+                .crossover(&self.population[father], &mut self.rng)
+                .mutated(
+                    settings.mutation_rate,
+                    settings.mutation_strength,
+                    &mut self.rng,
+                );
+            next.push(child);
+        }
+
+        self.population = next;
+    }
+
+    /// Index of the fittest of a few genomes picked at random.
+    fn select(&mut self) -> usize {
+        (0..self.settings.tournament_size)
+            .map(|_| self.rng.range(0, self.population.len()))
+            .max_by(|&a, &b| {
+                self.population[a]
+                    .fitness
+                    .total_cmp(&self.population[b].fitness)
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Debug for Evolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Evolution")
+            .field("settings", &self.settings)
+            .field("reward", &self.reward)
+            .field("generation", &self.generation)
+            .field("population", &self.population.len())
+            .field("champion", &self.champion)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,9 @@
+mod env;
+mod evolution;
+mod reward;
+mod vec_env;
+
+pub use env::{Env, StepInfo};
+pub use evolution::{Evolution, EvolutionConfig, GenerationReport};
+pub use reward::RewardConfig;
+pub use vec_env::{VecEnv, VecStep};
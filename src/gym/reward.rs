@@ -0,0 +1,48 @@
+use crate::config::{REWARD_CRASH, REWARD_GAP_DISTANCE, REWARD_OBSTACLE_PASSED, REWARD_SURVIVAL};
+use serde::{Deserialize, Serialize};
+
+/// Weights of the terms that make up an [`Env`](crate::gym::Env) reward.
+///
+/// Each tick's reward is the sum of:
+///
+/// - `survival` for every tick survived
+/// - `obstacle_passed` for every obstacle cleared
+/// - `-gap_distance` times the player's distance from the center of the
+///   next gap, as a fraction of the screen height
+/// - `crash` once, on the tick the run ends in a crash
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::gym::RewardConfig;
+///
+/// let pipes_only = RewardConfig {
+///     survival: 0.0,
+///     gap_distance: 0.0,
+///     ..RewardConfig::default()
+/// };
+/// assert_eq!(pipes_only.obstacle_passed, RewardConfig::default().obstacle_passed);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RewardConfig {
+    /// Reward for every tick survived.
+    pub survival: f32,
+    /// Reward for every obstacle cleared.
+    pub obstacle_passed: f32,
+    /// Penalty per screen height of distance from the next gap's center.
+    pub gap_distance: f32,
+    /// Reward on crashing; usually negative.
+    pub crash: f32,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        Self {
+            survival: REWARD_SURVIVAL,
+            obstacle_passed: REWARD_OBSTACLE_PASSED,
+            gap_distance: REWARD_GAP_DISTANCE,
+            crash: REWARD_CRASH,
+        }
+    }
+}
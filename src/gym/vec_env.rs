@@ -0,0 +1,187 @@
+use crate::agent::Features;
+use crate::config::{GameConfig, VEC_ENV_MIN_ENVS_PER_THREAD};
+use crate::error::GameResult;
+use crate::game::TickInput;
+use crate::gym::{Env, RewardConfig, StepInfo};
+use std::thread;
+
+/// Results of stepping every environment of a [`VecEnv`] once, indexed
+/// like the environments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VecStep {
+    pub observations: Vec<Features>,
+    pub rewards: Vec<f32>,
+    pub dones: Vec<bool>,
+    pub infos: Vec<StepInfo>,
+}
+
+/// Many [`Env`]s stepped together, spread over the available CPU cores.
+///
+/// Each worker thread gets at least [`VEC_ENV_MIN_ENVS_PER_THREAD`]
+/// environments, so small batches run on the calling thread instead of
+/// paying for new threads every step.
+///
+/// Environments are not reset automatically; stepping one whose episode
+/// is done keeps reporting it as done until it is reset.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::TickInput;
+/// use flappycheems::gym::VecEnv;
+///
+/// let mut envs = VecEnv::new(GameConfig::default(), 4)?;
+/// let observations = envs.reset(&[1, 2, 3, 4]);
+/// assert_eq!(observations.len(), 4);
+///
+/// let step = envs.step(&[TickInput::IDLE; 4]);
+/// assert_eq!(step.rewards.len(), 4);
+/// assert!(step.dones.iter().all(|done| !done));
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct VecEnv {
+    envs: Vec<Env>,
+    /// Most worker threads to use, or `None` for one per CPU core.
+    max_threads: Option<usize>,
+}
+
+impl VecEnv {
+    /// Creates `count` environments that play by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config` describes an unplayable game.
+    pub fn new(config: GameConfig, count: usize) -> GameResult<Self> {
+        let env = Env::new(config)?;
+        Ok(Self {
+            envs: vec![env; count],
+            max_threads: None,
+        })
+    }
+
+    /// Uses `reward` to score steps in every environment.
+    pub fn with_reward(mut self, reward: RewardConfig) -> Self {
+        self.envs = self
+            .envs
+            .into_iter()
+            .map(|env| env.with_reward(reward))
+            .collect();
+        self
+    }
+
+    /// Ends episodes after `max_ticks` ticks in every environment.
+    pub fn with_max_ticks(mut self, max_ticks: u64) -> Self {
+        self.envs = self
+            .envs
+            .into_iter()
+            .map(|env| env.with_max_ticks(max_ticks))
+            .collect();
+        self
+    }
+
+    /// Uses at most `max_threads` worker threads instead of one per CPU
+    /// core. Each still gets at least [`VEC_ENV_MIN_ENVS_PER_THREAD`]
+    /// environments.
+    pub fn with_max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = Some(max_threads);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.envs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.envs.is_empty()
+    }
+
+    pub fn envs(&self) -> &[Env] {
+        &self.envs
+    }
+
+    /// Whether every environment's episode has ended.
+    pub fn all_done(&self) -> bool {
+        self.envs.iter().all(Env::is_done)
+    }
+
+    /// Starts a new episode in every environment, each on its own seed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seeds` does not hold one seed per environment.
+    pub fn reset(&mut self, seeds: &[u64]) -> Vec<Features> {
+        assert_eq!(seeds.len(), self.envs.len(), "one seed per environment");
+
+        self.map_envs(|env, index| env.reset(seeds[index]))
+    }
+
+    /// Starts a new episode in environment `index` only.
+    pub fn reset_env(&mut self, index: usize, seed: u64) -> Features {
+        self.envs[index].reset(seed)
+    }
+
+    /// Applies one action to each environment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `actions` does not hold one action per environment.
+    pub fn step(&mut self, actions: &[TickInput]) -> VecStep {
+        assert_eq!(actions.len(), self.envs.len(), "one action per environment");
+
+        let results = self.map_envs(|env, index| env.step(actions[index]));
+        let mut step = VecStep::default();
+        for (observation, reward, done, info) in results {
+            step.observations.push(observation);
+            step.rewards.push(reward);
+            step.dones.push(done);
+            step.infos.push(info);
+        }
+        step
+    }
+
+    /// Runs `f` on every environment with its index, in parallel chunks.
+    fn map_envs<T, F>(&mut self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&mut Env, usize) -> T + Sync,
+    {
+        let threads = self
+            .max_threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from))
+            .min(self.envs.len() / VEC_ENV_MIN_ENVS_PER_THREAD);
+        if threads <= 1 {
+            return self
+                .envs
+                .iter_mut()
+                .enumerate()
+                .map(|(index, env)| f(env, index))
+                .collect();
+        }
+
+        let chunk_size = self.envs.len().div_ceil(threads);
+        let f = &f;
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .envs
+                .chunks_mut(chunk_size)
+                .enumerate()
+                .map(|(chunk, envs)| {
+                    scope.spawn(move || {
+                        envs.iter_mut()
+                            .enumerate()
+                            .map(|(offset, env)| f(env, chunk * chunk_size + offset))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("environment thread panicked"))
+                .collect()
+        })
+    }
+}
@@ -18,6 +18,10 @@
 //! - **Player**: Handles character physics, animation, and rendering
 //! - **Environment**: Manages obstacles and collision detection
 //! - **Game**: Coordinates game states, scoring, and transitions
+//! - **Agent**: Bots that play the game on their own
+//! - **Gym**: Training environments for learning agents
+//! - **Net**: Networked races between two games
+//! - **Render**: The screens the game draws on, in a window or in memory
 //! - **Config**: Centralizes all game constants and configuration
 //! - **Error**: Provides comprehensive error handling
 //!
@@ -32,28 +36,45 @@
 //! }
 //! ```
 
+// bracket-lib cannot draw through two back-ends at once, and Cargo features
+// only ever add, so `terminal` cannot switch the default `opengl` off.
+#[cfg(all(feature = "opengl", feature = "terminal"))]
+compile_error!(
+    "the `opengl` and `terminal` features are exclusive; \
+     build the terminal version with `--no-default-features --features terminal`"
+);
+
+pub mod agent;
 pub mod config;
 pub mod environment;
 pub mod error;
 pub mod game;
+pub mod gym;
+pub mod net;
 pub mod player;
+pub mod render;
 
+use crate::agent::{Agent, Observation};
+use crate::config::{ASSET_EMBED_DIR, ASSET_FILE_FONT, ASSET_FILE_SPRITES, GameConfig};
 use crate::error::{GameError, GameResult};
-use crate::game::GameState;
-use bracket_lib::prelude::*;
-use config::{
-    ASSET_PATH_FONT, ASSET_PATH_SPRITES, CONSOLE_TILE_SIZE, GAME_TITLE, SCREEN_HEIGHT,
-    SCREEN_WIDTH, TILE_SIZE,
+use crate::game::{
+    GameState, Replay, Simulation, SpectatorMode, TickInput, default_high_score_path,
+    default_replay_dir,
 };
+use crate::net::{NetSession, SpectatorClient, SpectatorServer};
+use crate::player::SkinRegistry;
+use bracket_lib::prelude::*;
 
 /// Runs the Flappy Cheems game.
 ///
-/// This function initializes the game context, creates the game state,
-/// and starts the main game loop.
+/// This function loads the configuration from the standard locations,
+/// initializes the game context, creates the game state, and starts the
+/// main game loop.
 ///
 /// # Errors
 ///
 /// Returns a `GameError` if:
+/// - A configuration file exists but cannot be loaded
 /// - The game context cannot be created
 /// - Asset files cannot be loaded
 /// - The main game loop encounters an error
@@ -69,13 +90,351 @@ use config::{
 /// }
 /// ```
 pub fn run_game() -> GameResult<()> {
-    let context = create_game_context()?;
-    let game_state = GameState::new();
+    run_game_with_config(GameConfig::discover()?, None)
+}
+
+/// Runs the Flappy Cheems game with an explicit configuration.
+///
+/// If `seed` is given, every run is played on the course of that seed.
+///
+/// # Errors
+///
+/// Returns a `GameError` if the configuration fails
+/// [`GameConfig::validate`], the game context cannot be created or the
+/// main game loop encounters an error.
+pub fn run_game_with_config(config: GameConfig, seed: Option<u64>) -> GameResult<()> {
+    run_game_with_agents(config, seed, None, None)
+}
+
+/// Runs the Flappy Cheems game with bots taking part.
+///
+/// `autopilot`, if given, flies the player instead of the keyboard, and
+/// `opponent`, if given, races the player on the same course in every run.
+///
+/// # Errors
+///
+/// Returns the same errors as [`run_game_with_config`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use flappycheems::agent::{Genome, NeuralAgent};
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::run_game_with_agents;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let genome = Genome::load("genomes/best.toml")?;
+///     let opponent = Box::new(NeuralAgent::new(genome));
+///     run_game_with_agents(GameConfig::default(), None, None, Some(opponent))?;
+///     Ok(())
+/// }
+/// ```
+pub fn run_game_with_agents(
+    config: GameConfig,
+    seed: Option<u64>,
+    autopilot: Option<Box<dyn Agent>>,
+    opponent: Option<Box<dyn Agent>>,
+) -> GameResult<()> {
+    run_game_with_spectators(config, seed, autopilot, opponent, None)
+}
+
+/// Runs the Flappy Cheems game with bots taking part, as in
+/// [`run_game_with_agents`], and broadcasts every run through `spectators`.
+///
+/// Spectators connect to the server's address, for example with
+/// [`run_spectator`], and follow each run tick by tick.
+///
+/// # Errors
+///
+/// Returns the same errors as [`run_game_with_config`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::net::SpectatorServer;
+/// use flappycheems::run_broadcast_game;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let spectators = SpectatorServer::bind("127.0.0.1:7878")?;
+///     run_broadcast_game(GameConfig::default(), None, None, None, spectators)?;
+///     Ok(())
+/// }
+/// ```
+pub fn run_broadcast_game(
+    config: GameConfig,
+    seed: Option<u64>,
+    autopilot: Option<Box<dyn Agent>>,
+    opponent: Option<Box<dyn Agent>>,
+    spectators: SpectatorServer,
+) -> GameResult<()> {
+    run_game_with_spectators(config, seed, autopilot, opponent, Some(spectators))
+}
+
+fn run_game_with_spectators(
+    config: GameConfig,
+    seed: Option<u64>,
+    autopilot: Option<Box<dyn Agent>>,
+    opponent: Option<Box<dyn Agent>>,
+    spectators: Option<SpectatorServer>,
+) -> GameResult<()> {
+    config.validate()?;
+    let mut game_state = create_game_state(config, seed);
+    if let Some(agent) = autopilot {
+        game_state.set_autopilot(agent);
+    }
+    if let Some(agent) = opponent {
+        game_state.set_opponent(agent);
+    }
+    if let Some(server) = spectators {
+        game_state.set_spectators(server);
+    }
+
+    let context = create_game_context(game_state.config())?;
+
+    main_loop(context, game_state).map_err(GameError::BracketLib)?;
+    Ok(())
+}
+
+/// Watches the games broadcast at `addr` in a window.
+///
+/// The window takes the size of the broadcasting game and follows it from
+/// run to run until it closes. Settings that only concern this machine,
+/// such as the asset directory, are taken from `local`.
+///
+/// # Errors
+///
+/// Returns a `GameError` if the broadcast cannot be joined or the game
+/// context cannot be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::run_spectator;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     run_spectator("127.0.0.1:7878", &GameConfig::discover()?)?;
+///     Ok(())
+/// }
+/// ```
+pub fn run_spectator(addr: impl std::net::ToSocketAddrs, local: &GameConfig) -> GameResult<()> {
+    let client = SpectatorClient::connect(addr)?;
+    let mut config = client.config().clone();
+    config.apply_local(local);
+    let context = create_game_context(&config)?;
+
+    main_loop(context, SpectatorMode::new(client)).map_err(GameError::BracketLib)?;
+    Ok(())
+}
+
+/// Races the run recorded in a replay file on the same course.
+///
+/// Every run is played on the replay's seed and configuration, with the
+/// recorded run shown as a ghost next to the player. Once a run beats the
+/// replay, the ghost of the better run takes its place. Settings that only
+/// concern this machine are taken from `local`.
+///
+/// # Errors
+///
+/// Returns a `GameError` if the replay file cannot be loaded or the game
+/// context cannot be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::run_ghost_race;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     run_ghost_race("replays/1700000000-42.toml", &GameConfig::discover()?)?;
+///     Ok(())
+/// }
+/// ```
+pub fn run_ghost_race(path: impl AsRef<std::path::Path>, local: &GameConfig) -> GameResult<()> {
+    let replay = Replay::load(path)?;
+    let mut config = replay.config.clone();
+    config.apply_local(local);
+    let mut game_state = create_game_state(config, Some(replay.seed));
+    game_state.set_ghost(replay);
+
+    let context = create_game_context(game_state.config())?;
+
+    main_loop(context, game_state).map_err(GameError::BracketLib)?;
+    Ok(())
+}
+
+/// Races the rival of `session` over the network.
+///
+/// The run is played on the seed and configuration agreed in the
+/// session's handshake, with the settings that only concern this machine
+/// taken from `local`. `autopilot`, if given, flies the local player.
+///
+/// # Errors
+///
+/// Returns a `GameError` if the game context cannot be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::net::NetSession;
+/// use flappycheems::run_network_game;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let session = NetSession::join("127.0.0.1:7777")?;
+///     run_network_game(session, &GameConfig::discover()?, None)?;
+///     Ok(())
+/// }
+/// ```
+pub fn run_network_game(
+    session: NetSession,
+    local: &GameConfig,
+    autopilot: Option<Box<dyn Agent>>,
+) -> GameResult<()> {
+    let mut config = session.config().clone();
+    config.apply_local(local);
+    let mut game_state = create_game_state(config, Some(session.seed()));
+    if let Some(agent) = autopilot {
+        game_state.set_autopilot(agent);
+    }
+
+    let context = create_game_context(game_state.config())?;
+    game_state.start_network(session);
 
     main_loop(context, game_state).map_err(GameError::BracketLib)?;
     Ok(())
 }
 
+/// Plays a recorded replay file back in the game window.
+///
+/// The run is driven entirely by the recorded inputs and plays by the
+/// configuration stored in the replay, with the settings that only
+/// concern this machine taken from `local`; once it ends the regular
+/// game-over menu is shown.
+///
+/// # Errors
+///
+/// Returns a `GameError` if the replay file cannot be loaded or the game
+/// context cannot be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::run_replay;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     run_replay("replays/1700000000-42.toml", &GameConfig::discover()?)?;
+///     Ok(())
+/// }
+/// ```
+pub fn run_replay(path: impl AsRef<std::path::Path>, local: &GameConfig) -> GameResult<()> {
+    let mut replay = Replay::load(path)?;
+    replay.config.apply_local(local);
+    let context = create_game_context(&replay.config)?;
+    let mut game_state = GameState::with_config(replay.config.clone(), None);
+    game_state.start_replay(replay);
+
+    main_loop(context, game_state).map_err(GameError::BracketLib)?;
+    Ok(())
+}
+
+/// Plays a single run without a window and returns its final state.
+///
+/// `controller` is asked for the input of every tick. The run stops when
+/// the player crashes or after `max_ticks` ticks, whichever comes first.
+///
+/// # Errors
+///
+/// Returns a `GameError` if the configuration fails [`GameConfig::validate`].
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::TickInput;
+/// use flappycheems::run_headless;
+///
+/// let simulation = run_headless(GameConfig::default(), 42, 1_000, |_| TickInput::IDLE)?;
+/// assert!(simulation.is_game_over());
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+pub fn run_headless<F>(
+    config: GameConfig,
+    seed: u64,
+    max_ticks: u64,
+    mut controller: F,
+) -> GameResult<Simulation>
+where
+    F: FnMut(&Simulation) -> TickInput,
+{
+    config.validate()?;
+    let mut simulation = Simulation::with_config(config, seed);
+
+    while !simulation.is_game_over() && simulation.tick() < max_ticks {
+        let input = controller(&simulation);
+        simulation.step(input);
+    }
+
+    Ok(simulation)
+}
+
+/// Lets `agent` play a run of `config` on the course of `seed` without
+/// opening a window.
+///
+/// The agent is reset before the run starts, and the run stops after
+/// `max_ticks` ticks if the agent has not crashed by then.
+///
+/// # Errors
+///
+/// Returns an error if `config` describes an unplayable game.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::agent::LookaheadAgent;
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::run_agent;
+///
+/// let mut agent = LookaheadAgent::new();
+/// let simulation = run_agent(GameConfig::default(), 42, 200, &mut agent)?;
+/// assert!(!simulation.is_game_over());
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+pub fn run_agent<A>(
+    config: GameConfig,
+    seed: u64,
+    max_ticks: u64,
+    agent: &mut A,
+) -> GameResult<Simulation>
+where
+    A: Agent + ?Sized,
+{
+    agent.reset();
+    run_headless(config, seed, max_ticks, |simulation| {
+        agent.act(&Observation::of(simulation))
+    })
+}
+
+/// Creates a game state that offers the user's characters and saves
+/// replays and high scores in the user's data directory.
+///
+/// An unreadable character registry is reported on stderr and replaced by
+/// the built-in characters, since it only changes how the player looks.
+fn create_game_state(config: GameConfig, seed: Option<u64>) -> GameState {
+    let mut game_state = GameState::with_config(config, seed);
+    game_state.set_skins(SkinRegistry::discover().unwrap_or_else(|e| {
+        eprintln!("Using the built-in characters: {}", e);
+        SkinRegistry::builtin()
+    }));
+    game_state.set_replay_dir(default_replay_dir());
+    if let Some(path) = default_high_score_path() {
+        game_state.load_high_scores(path);
+    }
+    game_state
+}
+
 /// Creates and configures the game context.
 ///
 /// This function sets up the bracket-lib terminal with the appropriate
@@ -85,15 +444,57 @@ pub fn run_game() -> GameResult<()> {
 ///
 /// Returns a `GameError::BracketLib` if the terminal builder fails
 /// to create the context, typically due to missing asset files.
-fn create_game_context() -> GameResult<BTerm> {
-    let context = BTermBuilder::new()
-        .with_title(GAME_TITLE)
-        .with_font(ASSET_PATH_FONT, TILE_SIZE, TILE_SIZE)
-        .with_simple_console(SCREEN_WIDTH, SCREEN_HEIGHT, ASSET_PATH_SPRITES)
-        .with_fancy_console(SCREEN_WIDTH, SCREEN_HEIGHT, ASSET_PATH_SPRITES)
-        .with_tile_dimensions(CONSOLE_TILE_SIZE, CONSOLE_TILE_SIZE)
+fn create_game_context(config: &GameConfig) -> GameResult<BTerm> {
+    let display = &config.display;
+    let assets = &config.assets;
+
+    let resource_dir = assets.resource_dir()?;
+    link_embedded_assets();
+
+    let builder = BTermBuilder::new()
+        .with_title(&display.title)
+        .with_resource_path(resource_dir)
+        .with_font(&assets.font, display.tile_size, display.tile_size)
+        .with_simple_console(display.screen_width, display.screen_height, &assets.sprites);
+
+    let context = with_sprite_layer(builder, config)
+        .with_tile_dimensions(display.console_tile_size, display.console_tile_size)
         .build()
         .map_err(GameError::BracketLib)?;
 
     Ok(context)
 }
+
+embedded_resource!(SPRITE_SHEET, "../resources/flappycheems.png");
+
+/// Makes the sprite sheet built into the binary available to bracket-lib
+/// under the default asset names.
+fn link_embedded_assets() {
+    for file in [ASSET_FILE_FONT, ASSET_FILE_SPRITES] {
+        let path = format!("{}/{}", ASSET_EMBED_DIR, file);
+        link_resource!(SPRITE_SHEET, path);
+    }
+}
+
+/// Adds the console sprites are drawn on, console 1.
+#[cfg(not(feature = "terminal"))]
+fn with_sprite_layer(builder: BTermBuilder, config: &GameConfig) -> BTermBuilder {
+    let display = &config.display;
+    builder.with_fancy_console(
+        display.screen_width,
+        display.screen_height,
+        &config.assets.sprites,
+    )
+}
+
+/// Adds an empty console in place of the sprite layer, which terminals
+/// cannot draw, so that console 1 still exists.
+#[cfg(feature = "terminal")]
+fn with_sprite_layer(builder: BTermBuilder, config: &GameConfig) -> BTermBuilder {
+    let display = &config.display;
+    builder.with_sparse_console_no_bg(
+        display.screen_width,
+        display.screen_height,
+        &config.assets.sprites,
+    )
+}
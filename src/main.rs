@@ -1,9 +1,306 @@
-use flappycheems::run_game;
+use clap::Parser;
+use flappycheems::agent::{Agent, Genome, LookaheadAgent, NeuralAgent};
+use flappycheems::config::GameConfig;
+use flappycheems::error::{GameError, GameResult};
+use flappycheems::game::{DifficultyPreset, PlayMode, Replay, Simulation, TickInput, random_seed};
+use flappycheems::net::{NetSession, SpectatorServer};
+use flappycheems::{
+    run_agent, run_broadcast_game, run_game_with_agents, run_ghost_race, run_headless,
+    run_network_game, run_replay, run_spectator,
+};
+use std::path::{Path, PathBuf};
 use std::process;
 
+/// Tick limit for headless runs when `--ticks` is not given.
+const DEFAULT_HEADLESS_TICKS: u64 = 10_000;
+
+/// A Flappy Bird clone featuring Cheems.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Seed for the obstacle course; a random seed is drawn if omitted.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Configuration file to load instead of searching the default locations.
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Rule set to play under (endless, practice).
+    #[arg(long, value_name = "MODE")]
+    mode: Option<PlayMode>,
+
+    /// Difficulty preset to play on (easy, normal, hard).
+    #[arg(long, value_name = "PRESET")]
+    difficulty: Option<DifficultyPreset>,
+
+    /// Replay file to play back.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["seed", "config", "mode", "difficulty", "width", "height"])]
+    replay: Option<PathBuf>,
+
+    /// Replay file to race as a ghost on its own course.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "replay", "headless", "autopilot", "opponent",
+            "seed", "config", "mode", "difficulty", "width", "height",
+        ]
+    )]
+    ghost: Option<PathBuf>,
+
+    /// Address to open a network lobby on, racing whoever joins it.
+    #[arg(
+        long,
+        value_name = "ADDR",
+        conflicts_with_all = ["join", "replay", "ghost", "headless", "opponent"]
+    )]
+    host: Option<String>,
+
+    /// Address of a network lobby to join; the host picks course and rules.
+    #[arg(
+        long,
+        value_name = "ADDR",
+        conflicts_with_all = [
+            "replay", "ghost", "headless", "opponent",
+            "seed", "config", "mode", "difficulty", "width", "height",
+        ]
+    )]
+    join: Option<String>,
+
+    /// Address to broadcast every run on, for `--spectate` or telemetry tools.
+    #[arg(
+        long,
+        value_name = "ADDR",
+        conflicts_with_all = ["replay", "ghost", "headless", "host", "join"]
+    )]
+    broadcast: Option<String>,
+
+    /// Address of a broadcasting game to watch instead of playing.
+    #[arg(
+        long,
+        value_name = "ADDR",
+        conflicts_with_all = [
+            "replay", "ghost", "headless", "host", "join", "broadcast", "autopilot", "opponent",
+            "seed", "config", "mode", "difficulty", "width", "height",
+        ]
+    )]
+    spectate: Option<String>,
+
+    /// Run without opening a window and print the result.
+    #[arg(long)]
+    headless: bool,
+
+    /// Maximum number of ticks to simulate in a headless run.
+    #[arg(long, value_name = "N", requires = "headless")]
+    ticks: Option<u64>,
+
+    /// Let the lookahead bot play headless runs instead of idling.
+    #[arg(long, requires = "headless", conflicts_with = "replay")]
+    bot: bool,
+
+    /// Trained genome that flies the player, in the window or headless.
+    #[arg(long, value_name = "GENOME", conflicts_with_all = ["replay", "bot"])]
+    autopilot: Option<PathBuf>,
+
+    /// Trained genome that races the player on the same course.
+    #[arg(long, value_name = "GENOME", conflicts_with_all = ["replay", "headless"])]
+    opponent: Option<PathBuf>,
+
+    /// Number of headless runs, on consecutive seeds from `--seed`.
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "headless",
+        conflicts_with = "replay"
+    )]
+    runs: Option<u64>,
+
+    /// Screen width in characters.
+    #[arg(long)]
+    width: Option<i32>,
+
+    /// Screen height in characters.
+    #[arg(long)]
+    height: Option<i32>,
+
+    /// Directory to load the sprite sheets from instead of the built-in ones.
+    #[arg(long, value_name = "DIR")]
+    asset_dir: Option<PathBuf>,
+}
+
+impl Cli {
+    fn load_config(&self) -> GameResult<GameConfig> {
+        let mut config = match &self.config {
+            Some(path) => GameConfig::load(path)?,
+            None => GameConfig::discover()?,
+        };
+
+        if let Some(mode) = self.mode {
+            config.mode = mode;
+        }
+        if let Some(preset) = self.difficulty {
+            config.difficulty.preset = preset;
+        }
+        if let Some(width) = self.width {
+            config.display.screen_width = width;
+        }
+        if let Some(height) = self.height {
+            config.display.screen_height = height;
+        }
+        if let Some(dir) = &self.asset_dir {
+            config.assets.dir = Some(dir.clone());
+        }
+
+        Ok(config)
+    }
+}
+
 fn main() {
-    if let Err(e) = run_game() {
+    let cli = Cli::parse();
+
+    if let Err(e) = run(&cli) {
         eprintln!("Game error: {}", e);
         process::exit(1);
     }
 }
+
+fn run(cli: &Cli) -> GameResult<()> {
+    if let Some(path) = &cli.ghost {
+        return run_ghost_race(path, &cli.load_config()?);
+    }
+    if let Some(addr) = &cli.spectate {
+        return run_spectator(addr.as_str(), &cli.load_config()?);
+    }
+    if let Some(addr) = &cli.broadcast {
+        let spectators = SpectatorServer::bind(addr.as_str())?;
+        if let Some(local_addr) = spectators.local_addr() {
+            println!("Broadcasting runs on {}", local_addr);
+        }
+        return run_broadcast_game(
+            cli.load_config()?,
+            cli.seed,
+            load_neural_agent(cli.autopilot.as_deref())?,
+            load_neural_agent(cli.opponent.as_deref())?,
+            spectators,
+        );
+    }
+    if let Some(addr) = &cli.host {
+        return host_network_game(cli, addr);
+    }
+    if let Some(addr) = &cli.join {
+        let session = NetSession::join(addr.as_str())?;
+        return run_network_game(
+            session,
+            &cli.load_config()?,
+            load_neural_agent(cli.autopilot.as_deref())?,
+        );
+    }
+
+    match (&cli.replay, cli.headless) {
+        (Some(path), true) => verify_replay(path),
+        (Some(path), false) => run_replay(path, &cli.load_config()?),
+        (None, true) => run_headless_game(cli),
+        (None, false) => run_game_with_agents(
+            cli.load_config()?,
+            cli.seed,
+            load_neural_agent(cli.autopilot.as_deref())?,
+            load_neural_agent(cli.opponent.as_deref())?,
+        ),
+    }
+}
+
+fn host_network_game(cli: &Cli, addr: &str) -> GameResult<()> {
+    let config = cli.load_config()?;
+    let seed = cli.seed.unwrap_or_else(random_seed);
+    let autopilot = load_neural_agent(cli.autopilot.as_deref())?;
+
+    let listener = NetSession::listen(addr)?;
+    if let Ok(local_addr) = listener.local_addr() {
+        println!(
+            "Waiting for a rival to join {} (seed {})...",
+            local_addr, seed
+        );
+    }
+    let session = NetSession::accept(&listener, config.clone(), seed)?;
+
+    run_network_game(session, &config, autopilot)
+}
+
+fn load_neural_agent(path: Option<&Path>) -> GameResult<Option<Box<dyn Agent>>> {
+    path.map(|path| Ok(Box::new(NeuralAgent::new(Genome::load(path)?)) as Box<dyn Agent>))
+        .transpose()
+}
+
+fn run_headless_game(cli: &Cli) -> GameResult<()> {
+    let config = cli.load_config()?;
+    let first_seed = cli.seed.unwrap_or_else(random_seed);
+    let ticks = cli.ticks.unwrap_or(DEFAULT_HEADLESS_TICKS);
+    let runs = cli.runs.unwrap_or(1).max(1);
+    let mut agent = match load_neural_agent(cli.autopilot.as_deref())? {
+        Some(agent) => Some(agent),
+        None if cli.bot => Some(Box::new(LookaheadAgent::new()) as Box<dyn Agent>),
+        None => None,
+    };
+    let mut scores = Vec::new();
+    let mut crashes = 0;
+
+    for run in 0..runs {
+        let seed = first_seed.wrapping_add(run);
+        let simulation = match &mut agent {
+            Some(agent) => run_agent(config.clone(), seed, ticks, agent.as_mut())?,
+            None => run_headless(config.clone(), seed, ticks, |_| TickInput::IDLE)?,
+        };
+
+        print_run(&simulation);
+        scores.push(simulation.score());
+        crashes += u32::from(simulation.is_game_over());
+    }
+
+    if runs > 1 {
+        let total: i64 = scores.iter().map(|&score| i64::from(score)).sum();
+        println!(
+            "runs={} agent={} mean_score={:.2} min_score={} max_score={} crashes={}",
+            runs,
+            agent.as_ref().map_or("idle", |agent| agent.name()),
+            total as f64 / runs as f64,
+            scores.iter().min().copied().unwrap_or_default(),
+            scores.iter().max().copied().unwrap_or_default(),
+            crashes
+        );
+    }
+    Ok(())
+}
+
+fn print_run(simulation: &Simulation) {
+    println!(
+        "seed={} ticks={} score={} game_over={}",
+        simulation.seed(),
+        simulation.tick(),
+        simulation.score(),
+        simulation.is_game_over()
+    );
+}
+
+fn verify_replay(path: &Path) -> GameResult<()> {
+    let replay = Replay::load(path)?;
+    let simulation = replay.simulate();
+
+    println!(
+        "seed={} ticks={} score={} recorded_score={}",
+        replay.seed,
+        simulation.tick(),
+        simulation.score(),
+        replay.final_score
+    );
+
+    if simulation.score() != replay.final_score {
+        return Err(GameError::ReplayMismatch {
+            path: path.display().to_string(),
+            recorded: replay.final_score,
+            replayed: simulation.score(),
+        });
+    }
+
+    Ok(())
+}
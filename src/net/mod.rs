@@ -0,0 +1,21 @@
+//! Networked head-to-head races.
+//!
+//! A host opens a lobby with [`NetSession::listen`] and waits in
+//! [`NetSession::accept`]; the rival connects with [`NetSession::join`].
+//! The host's seed and configuration are sent in the handshake, after
+//! which both games only exchange the inputs of their players, tagged
+//! with tick numbers and state hashes.
+//!
+//! A game can also broadcast its runs to spectators through a
+//! [`SpectatorServer`], which [`SpectatorClient`] follows.
+
+mod protocol;
+mod session;
+mod spectator;
+
+pub use protocol::{Message, NET_PROTOCOL_VERSION};
+pub use session::{NetSession, PeerStatus};
+pub use spectator::{
+    PlayerFrame, SPECTATOR_PROTOCOL_VERSION, SpectatorClient, SpectatorMessage, SpectatorServer,
+    TickFrame,
+};
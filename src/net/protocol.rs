@@ -0,0 +1,197 @@
+//! Wire format of networked races.
+//!
+//! Peers exchange [`Message`]s as JSON objects, one per line, over a TCP
+//! stream. After the lobby handshake only inputs travel: every tick a
+//! player plays is sent as its tick number, whether they flapped, and a
+//! [`Simulation::state_hash`](crate::game::Simulation::state_hash) of their
+//! run afterwards.
+
+use crate::config::GameConfig;
+use crate::error::{GameError, GameResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+/// Version of the network protocol spoken by this build.
+pub const NET_PROTOCOL_VERSION: u32 = 7;
+
+/// A single message between two peers.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::net::Message;
+///
+/// let message = Message::Input { tick: 3, flap: true, hash: 42 };
+/// let line = serde_json::to_string(&message).unwrap();
+/// assert_eq!(line, r#"{"type":"input","tick":3,"flap":true,"hash":42}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Message {
+    /// Sent by a joining player to open the lobby handshake.
+    Hello {
+        /// Protocol version the joining player speaks.
+        version: u32,
+    },
+    /// The host's answer to [`Message::Hello`], fixing the race's course.
+    Welcome {
+        /// Protocol version the host speaks.
+        version: u32,
+        /// Seed of the course both players fly.
+        seed: u64,
+        /// Configuration both players play by.
+        config: Box<GameConfig>,
+    },
+    /// The host's answer to a [`Message::Hello`] it cannot accept.
+    Reject {
+        /// Why the player was turned away.
+        reason: String,
+    },
+    /// The input a player applied on one tick of their run.
+    Input {
+        /// Index of the tick the input was applied on.
+        tick: u64,
+        /// Whether the player flapped.
+        flap: bool,
+        /// State hash of the player's run after the tick.
+        hash: u64,
+    },
+    /// Keeps the connection alive while a player sends no inputs.
+    Ping,
+    /// Sent by a player leaving the race.
+    Bye,
+}
+
+/// A TCP stream carrying one JSON message per line.
+///
+/// Outgoing lines are queued and written as far as the stream takes them,
+/// so a non-blocking stream never cuts a message off halfway.
+#[derive(Debug)]
+pub(crate) struct Connection {
+    stream: TcpStream,
+    peer: SocketAddr,
+    buffer: Vec<u8>,
+    outgoing: Vec<u8>,
+}
+
+impl Connection {
+    pub(crate) fn new(stream: TcpStream) -> GameResult<Self> {
+        let peer = stream.peer_addr().map_err(|e| GameError::Network {
+            peer: "unknown peer".into(),
+            reason: e.to_string(),
+        })?;
+
+        let connection = Self {
+            stream,
+            peer,
+            buffer: Vec::new(),
+            outgoing: Vec::new(),
+        };
+        connection
+            .stream
+            .set_nodelay(true)
+            .map_err(|e| connection.error(e))?;
+        Ok(connection)
+    }
+
+    pub(crate) fn peer(&self) -> SocketAddr {
+        self.peer
+    }
+
+    /// Builds a network error about this connection.
+    pub(crate) fn error(&self, reason: impl ToString) -> GameError {
+        GameError::Network {
+            peer: self.peer.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Queues `message` and writes as much of the queue as the stream
+    /// takes without blocking, if it is non-blocking.
+    pub(crate) fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.outgoing, message)?;
+        self.outgoing.push(b'\n');
+        self.flush()
+    }
+
+    /// Writes queued lines until they are all sent or the stream would
+    /// block; the rest stays queued for the next send or flush.
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Bytes queued but not yet written to the stream.
+    pub(crate) fn backlog(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    /// Waits up to `timeout` for the next message.
+    pub(crate) fn receive_blocking<T: DeserializeOwned>(
+        &mut self,
+        timeout: Duration,
+    ) -> io::Result<T> {
+        self.stream.set_nonblocking(false)?;
+        self.stream.set_read_timeout(Some(timeout))?;
+
+        loop {
+            if let Some(message) = self.next_buffered()? {
+                return Ok(message);
+            }
+            self.fill_buffer()?;
+        }
+    }
+
+    /// Returns the next message if one has fully arrived.
+    ///
+    /// The stream is switched to non-blocking mode, so this never waits.
+    pub(crate) fn try_receive<T: DeserializeOwned>(&mut self) -> io::Result<Option<T>> {
+        if let Some(message) = self.next_buffered()? {
+            return Ok(Some(message));
+        }
+
+        self.stream.set_nonblocking(true)?;
+        match self.fill_buffer() {
+            Ok(()) => self.next_buffered(),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads whatever the stream has, failing once the peer has closed it.
+    fn fill_buffer(&mut self) -> io::Result<()> {
+        let mut chunk = [0; 4096];
+        match self.stream.read(&mut chunk)? {
+            0 => Err(ErrorKind::UnexpectedEof.into()),
+            read => {
+                self.buffer.extend_from_slice(&chunk[..read]);
+                Ok(())
+            }
+        }
+    }
+
+    /// Parses the first complete line in the buffer, if there is one.
+    fn next_buffered<T: DeserializeOwned>(&mut self) -> io::Result<Option<T>> {
+        let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') else {
+            return Ok(None);
+        };
+
+        let line: Vec<u8> = self.buffer.drain(..=end).collect();
+        serde_json::from_slice(&line)
+            .map(Some)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+}
@@ -0,0 +1,305 @@
+use crate::config::{
+    GameConfig, NET_HANDSHAKE_TIMEOUT_MS, NET_PEER_TIMEOUT_MS, NET_PING_INTERVAL_MS,
+};
+use crate::error::{GameError, GameResult};
+use crate::game::{Simulation, TickInput};
+use crate::net::protocol::{Connection, Message, NET_PROTOCOL_VERSION};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+/// State of the link to the other player of a networked race.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerStatus {
+    /// Inputs are flowing and every state hash has matched so far.
+    Connected,
+    /// The rival left, or has not been heard from for too long.
+    Disconnected,
+    /// The rival's reported state stopped matching their inputs on `tick`,
+    /// so their run can no longer be followed.
+    Desynced {
+        /// Index of the first tick whose state hash did not match.
+        tick: u64,
+    },
+}
+
+/// One side of a networked race between two players on the same course.
+///
+/// Both players fly their own [`Simulation`] of the same seed and
+/// configuration, agreed on in a lobby handshake. Each tick a player plays
+/// is sent to the other side as an input; the session replays the rival's
+/// inputs on its own copy of their run and checks the state hash they sent
+/// against it, so a diverging build is caught on the first tick it differs.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::config::GameConfig;
+/// use flappycheems::game::{Simulation, TickInput};
+/// use flappycheems::net::{NetSession, PeerStatus};
+/// use std::thread;
+///
+/// let listener = NetSession::listen("127.0.0.1:0")?;
+/// let addr = listener.local_addr().unwrap();
+/// let host = thread::spawn(move || NetSession::accept(&listener, GameConfig::default(), 9));
+///
+/// let mut guest = NetSession::join(addr)?;
+/// let mut host = host.join().unwrap()?;
+/// assert_eq!(guest.seed(), 9);
+///
+/// let mut local = Simulation::with_seed(9);
+/// local.step(TickInput::FLAP);
+/// host.send_tick(0, TickInput::FLAP, &local);
+///
+/// while guest.remote().tick() == 0 {
+///     guest.poll();
+/// }
+/// assert_eq!(guest.remote().state_hash(), local.state_hash());
+/// assert_eq!(guest.status(), PeerStatus::Connected);
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+#[derive(Debug)]
+pub struct NetSession {
+    connection: Connection,
+    seed: u64,
+    config: GameConfig,
+    remote: Simulation,
+    status: PeerStatus,
+    last_received: Instant,
+    last_sent: Instant,
+}
+
+impl NetSession {
+    /// Opens a lobby on `addr` for a rival to join.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Network` if the address cannot be bound.
+    pub fn listen(addr: impl ToSocketAddrs) -> GameResult<TcpListener> {
+        TcpListener::bind(addr).map_err(|e| GameError::Network {
+            peer: "lobby".into(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Waits for a rival to join the lobby and starts a race on the course
+    /// of `seed` under `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config` describes an unplayable game, and
+    /// `GameError::Network` if the rival cannot be reached, does not greet
+    /// in time or speaks another protocol version.
+    pub fn accept(listener: &TcpListener, config: GameConfig, seed: u64) -> GameResult<Self> {
+        config.validate()?;
+
+        let (stream, _) = listener.accept().map_err(|e| GameError::Network {
+            peer: "lobby".into(),
+            reason: e.to_string(),
+        })?;
+        let mut connection = Connection::new(stream)?;
+
+        let timeout = Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MS);
+        let version = match connection.receive_blocking(timeout) {
+            Ok(Message::Hello { version }) => version,
+            Ok(message) => {
+                return Err(connection.error(format!("expected hello, got {:?}", message)));
+            }
+            Err(e) => return Err(connection.error(e)),
+        };
+
+        if version != NET_PROTOCOL_VERSION {
+            let reason = format!(
+                "unsupported protocol version {} (expected {})",
+                version, NET_PROTOCOL_VERSION
+            );
+            let _ = connection.send(&Message::Reject {
+                reason: reason.clone(),
+            });
+            return Err(connection.error(reason));
+        }
+
+        let welcome = Message::Welcome {
+            version: NET_PROTOCOL_VERSION,
+            seed,
+            config: Box::new(config.clone()),
+        };
+        connection.send(&welcome).map_err(|e| connection.error(e))?;
+
+        Ok(Self::new(connection, config, seed))
+    }
+
+    /// Joins the lobby at `addr` and races on the course its host picked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Network` if the host cannot be reached, does
+    /// not answer in time or rejects the player, and the matching
+    /// validation error if the host's configuration is not playable.
+    pub fn join(addr: impl ToSocketAddrs) -> GameResult<Self> {
+        let stream = TcpStream::connect(addr).map_err(|e| GameError::Network {
+            peer: "lobby".into(),
+            reason: e.to_string(),
+        })?;
+        let mut connection = Connection::new(stream)?;
+
+        connection
+            .send(&Message::Hello {
+                version: NET_PROTOCOL_VERSION,
+            })
+            .map_err(|e| connection.error(e))?;
+
+        let timeout = Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MS);
+        match connection.receive_blocking(timeout) {
+            Ok(Message::Welcome {
+                version,
+                seed,
+                config,
+            }) if version == NET_PROTOCOL_VERSION => {
+                config.validate()?;
+                Ok(Self::new(connection, *config, seed))
+            }
+            Ok(Message::Welcome { version, .. }) => Err(connection.error(format!(
+                "unsupported protocol version {} (expected {})",
+                version, NET_PROTOCOL_VERSION
+            ))),
+            Ok(Message::Reject { reason }) => Err(connection.error(reason)),
+            Ok(message) => Err(connection.error(format!("expected welcome, got {:?}", message))),
+            Err(e) => Err(connection.error(e)),
+        }
+    }
+
+    fn new(connection: Connection, config: GameConfig, seed: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            connection,
+            remote: Simulation::with_config(config.clone(), seed),
+            seed,
+            config,
+            status: PeerStatus::Connected,
+            last_received: now,
+            last_sent: now,
+        }
+    }
+
+    /// Seed of the course both players fly.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Configuration both players play by.
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Address of the rival.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.connection.peer()
+    }
+
+    pub fn status(&self) -> PeerStatus {
+        self.status
+    }
+
+    /// The rival's run, as far as their inputs have arrived.
+    pub fn remote(&self) -> &Simulation {
+        &self.remote
+    }
+
+    /// Returns `true` while the rival is connected and still flying.
+    pub fn is_rival_racing(&self) -> bool {
+        self.status == PeerStatus::Connected && !self.remote.is_game_over()
+    }
+
+    /// Tells the rival that `input` was applied on the tick with index
+    /// `tick`, leaving the local run in the state of `local`.
+    ///
+    /// A failed send marks the rival as disconnected.
+    pub fn send_tick(&mut self, tick: u64, input: TickInput, local: &Simulation) {
+        self.send(&Message::Input {
+            tick,
+            flap: input.flap,
+            hash: local.state_hash(),
+        });
+    }
+
+    /// Applies every input that has arrived from the rival, sends what is
+    /// still queued for them and keeps the connection alive.
+    ///
+    /// Never blocks. Call it regularly, including while the local player
+    /// is paused or has crashed, or the rival will time the session out.
+    pub fn poll(&mut self) {
+        if self.status == PeerStatus::Disconnected {
+            return;
+        }
+
+        loop {
+            match self.connection.try_receive() {
+                Ok(Some(message)) => {
+                    self.last_received = Instant::now();
+                    self.handle(message);
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    self.disconnect();
+                    return;
+                }
+            }
+        }
+
+        if self.connection.flush().is_err() {
+            self.disconnect();
+            return;
+        }
+
+        if self.last_received.elapsed() >= Duration::from_millis(NET_PEER_TIMEOUT_MS) {
+            self.disconnect();
+            return;
+        }
+
+        if self.last_sent.elapsed() >= Duration::from_millis(NET_PING_INTERVAL_MS) {
+            self.send(&Message::Ping);
+        }
+    }
+
+    fn handle(&mut self, message: Message) {
+        match message {
+            Message::Input { tick, flap, hash } if self.status == PeerStatus::Connected => {
+                if tick != self.remote.tick() || self.remote.is_game_over() {
+                    self.status = PeerStatus::Desynced { tick };
+                    return;
+                }
+
+                self.remote.step(TickInput { flap });
+                if self.remote.state_hash() != hash {
+                    self.status = PeerStatus::Desynced { tick };
+                }
+            }
+            Message::Bye => self.disconnect(),
+            _ => {}
+        }
+    }
+
+    fn send(&mut self, message: &Message) {
+        if self.status == PeerStatus::Disconnected {
+            return;
+        }
+
+        match self.connection.send(message) {
+            Ok(()) => self.last_sent = Instant::now(),
+            Err(_) => self.disconnect(),
+        }
+    }
+
+    /// Marks the rival as gone, unless the race already desynced.
+    fn disconnect(&mut self) {
+        if self.status == PeerStatus::Connected {
+            self.status = PeerStatus::Disconnected;
+        }
+    }
+}
+
+impl Drop for NetSession {
+    fn drop(&mut self) {
+        let _ = self.connection.send(&Message::Bye);
+    }
+}
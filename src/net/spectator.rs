@@ -0,0 +1,417 @@
+//! Live broadcast of a running game to spectators.
+//!
+//! A game publishing its runs through a [`SpectatorServer`] writes one JSON
+//! object per line to every connected spectator: a `start` line whenever a
+//! run begins, carrying its seed and configuration, then a `tick` line with
+//! the player, obstacles, pickups and score after every tick. Any tool
+//! that reads lines from a TCP socket can follow the stream;
+//! [`SpectatorClient`] turns it back into something the game's renderer
+//! can draw.
+
+use crate::config::{GameConfig, NET_HANDSHAKE_TIMEOUT_MS, NET_SPECTATOR_MAX_BACKLOG_BYTES};
+use crate::environment::{Obstacle, Pickup};
+use crate::error::{GameError, GameResult};
+use crate::game::Simulation;
+use crate::net::protocol::Connection;
+use crate::player::{Player, Position};
+use serde::{Deserialize, Serialize};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Version of the spectator stream written by this build.
+pub const SPECTATOR_PROTOCOL_VERSION: u32 = 4;
+
+/// A single line of the spectator stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SpectatorMessage {
+    /// A new run has begun.
+    Start {
+        /// Version of the stream format.
+        version: u32,
+        /// Seed of the run's obstacle course.
+        seed: u64,
+        /// Configuration the run is played with.
+        config: Box<GameConfig>,
+    },
+    /// The state of the run after a tick.
+    Tick(TickFrame),
+}
+
+/// Everything visible in a run after one of its ticks.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::game::Simulation;
+/// use flappycheems::net::TickFrame;
+///
+/// let simulation = Simulation::with_seed(5);
+/// let frame = TickFrame::of(&simulation);
+///
+/// assert_eq!(frame.tick, 0);
+/// assert_eq!(frame.player.x, simulation.player().x());
+/// assert_eq!(frame.obstacles.len(), simulation.obstacles().len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TickFrame {
+    /// Number of ticks the run has lasted.
+    pub tick: u64,
+    /// Score reached so far.
+    pub score: i32,
+    /// Whether the player has crashed.
+    pub game_over: bool,
+    /// Position and motion of the player.
+    pub player: PlayerFrame,
+    /// Obstacles still in the world, from left to right.
+    pub obstacles: Vec<Obstacle>,
+    /// Pickups still waiting on the course, from left to right.
+    pub pickups: Vec<Pickup>,
+}
+
+impl TickFrame {
+    /// Captures the current state of `simulation`.
+    pub fn of(simulation: &Simulation) -> Self {
+        let player = simulation.player();
+        Self {
+            tick: simulation.tick(),
+            score: simulation.score(),
+            game_over: simulation.is_game_over(),
+            player: PlayerFrame {
+                x: player.x(),
+                y: player.y(),
+                velocity: player.velocity(),
+            },
+            obstacles: simulation.obstacles().iter().cloned().collect(),
+            pickups: simulation.pickups().iter().cloned().collect(),
+        }
+    }
+}
+
+/// Position and motion of the player in a [`TickFrame`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlayerFrame {
+    /// World column of the player.
+    pub x: i32,
+    /// Height of the player, in rows from the top of the screen.
+    pub y: f32,
+    /// Vertical velocity; positive values fall.
+    pub velocity: f32,
+}
+
+/// Broadcasts the runs of a game to every spectator that connects.
+///
+/// Spectators are accepted whenever a frame is published or the server
+/// is polled, and are greeted with the current run and its last frame.
+/// Writes never block the game: lines a spectator cannot take yet are
+/// queued, and a spectator is dropped as soon as it disconnects or falls
+/// more than [`NET_SPECTATOR_MAX_BACKLOG_BYTES`] behind.
+#[derive(Debug)]
+pub struct SpectatorServer {
+    listener: TcpListener,
+    spectators: Vec<Connection>,
+    start: Option<SpectatorMessage>,
+    frame: Option<SpectatorMessage>,
+    last_tick: u64,
+}
+
+impl SpectatorServer {
+    /// Starts listening for spectators on `addr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Network` if the address cannot be bound.
+    pub fn bind(addr: impl ToSocketAddrs) -> GameResult<Self> {
+        let network_error = |e: std::io::Error| GameError::Network {
+            peer: "spectators".into(),
+            reason: e.to_string(),
+        };
+
+        let listener = TcpListener::bind(addr).map_err(network_error)?;
+        listener.set_nonblocking(true).map_err(network_error)?;
+
+        Ok(Self {
+            listener,
+            spectators: Vec::new(),
+            start: None,
+            frame: None,
+            last_tick: 0,
+        })
+    }
+
+    /// Address spectators connect to.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+
+    /// Number of spectators currently watching.
+    pub fn spectator_count(&self) -> usize {
+        self.spectators.len()
+    }
+
+    /// Sends the current state of `simulation` to every spectator.
+    ///
+    /// A `start` line is sent first whenever `simulation` is a different
+    /// run from the one published last, and to each newly connected
+    /// spectator.
+    pub fn publish(&mut self, simulation: &Simulation) {
+        let new_run = match &self.start {
+            Some(SpectatorMessage::Start { seed, config, .. }) => {
+                *seed != simulation.seed()
+                    || **config != *simulation.config()
+                    || simulation.tick() < self.last_tick
+            }
+            _ => true,
+        };
+        self.last_tick = simulation.tick();
+
+        if new_run {
+            let start = start_of(simulation);
+            self.send_to_all(&start);
+            self.start = Some(start);
+            self.frame = None;
+        }
+
+        self.accept_spectators();
+        let frame = SpectatorMessage::Tick(TickFrame::of(simulation));
+        self.send_to_all(&frame);
+        self.frame = Some(frame);
+    }
+
+    /// Accepts waiting spectators and sends them what is still queued,
+    /// without publishing a new frame.
+    ///
+    /// Call it every frame the game draws, including in menus and while
+    /// paused, so spectators can join at any time. If no run has been
+    /// published yet, `simulation` is announced as the current one.
+    pub fn poll(&mut self, simulation: &Simulation) {
+        if self.start.is_none() {
+            self.start = Some(start_of(simulation));
+            self.frame = Some(SpectatorMessage::Tick(TickFrame::of(simulation)));
+            self.last_tick = simulation.tick();
+        }
+
+        self.accept_spectators();
+        self.spectators
+            .retain_mut(|connection| connection.flush().is_ok());
+    }
+
+    fn accept_spectators(&mut self) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_err() {
+                continue;
+            }
+            let Ok(mut connection) = Connection::new(stream) else {
+                continue;
+            };
+
+            let greeted = [&self.start, &self.frame]
+                .into_iter()
+                .flatten()
+                .all(|message| connection.send(message).is_ok());
+            if greeted {
+                self.spectators.push(connection);
+            }
+        }
+    }
+
+    fn send_to_all(&mut self, message: &SpectatorMessage) {
+        self.spectators.retain_mut(|connection| {
+            connection.send(message).is_ok()
+                && connection.backlog() <= NET_SPECTATOR_MAX_BACKLOG_BYTES
+        });
+    }
+}
+
+/// The `start` line announcing the run of `simulation`.
+fn start_of(simulation: &Simulation) -> SpectatorMessage {
+    SpectatorMessage::Start {
+        version: SPECTATOR_PROTOCOL_VERSION,
+        seed: simulation.seed(),
+        config: Box::new(simulation.config().clone()),
+    }
+}
+
+/// Follows the broadcast of a [`SpectatorServer`].
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::game::{Simulation, TickInput};
+/// use flappycheems::net::{SpectatorClient, SpectatorServer};
+/// use std::thread;
+///
+/// let mut server = SpectatorServer::bind("127.0.0.1:0")?;
+/// let addr = server.local_addr().unwrap();
+/// let mut simulation = Simulation::with_seed(12);
+/// server.publish(&simulation);
+///
+/// let client = thread::spawn(move || SpectatorClient::connect(addr));
+/// while server.spectator_count() == 0 {
+///     server.publish(&simulation);
+/// }
+/// let mut client = client.join().unwrap()?;
+///
+/// simulation.step(TickInput::FLAP);
+/// server.publish(&simulation);
+/// while client.tick() < 1 {
+///     client.poll();
+/// }
+///
+/// assert_eq!(client.seed(), 12);
+/// assert_eq!(client.player().y(), simulation.player().y());
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+#[derive(Debug)]
+pub struct SpectatorClient {
+    connection: Connection,
+    seed: u64,
+    config: GameConfig,
+    player: Player,
+    obstacles: Vec<Obstacle>,
+    pickups: Vec<Pickup>,
+    tick: u64,
+    score: i32,
+    game_over: bool,
+    connected: bool,
+}
+
+impl SpectatorClient {
+    /// Connects to the broadcast at `addr` and waits for its current run.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Network` if the broadcast cannot be reached,
+    /// announces no run in time or uses another stream version, and the
+    /// matching validation error if the run's configuration is not
+    /// playable.
+    pub fn connect(addr: impl ToSocketAddrs) -> GameResult<Self> {
+        let stream = TcpStream::connect(addr).map_err(|e| GameError::Network {
+            peer: "broadcast".into(),
+            reason: e.to_string(),
+        })?;
+        let mut connection = Connection::new(stream)?;
+
+        let timeout = Duration::from_millis(NET_HANDSHAKE_TIMEOUT_MS);
+        loop {
+            match connection.receive_blocking(timeout) {
+                Ok(SpectatorMessage::Start {
+                    version,
+                    seed,
+                    config,
+                }) => {
+                    if version != SPECTATOR_PROTOCOL_VERSION {
+                        return Err(connection.error(format!(
+                            "unsupported stream version {} (expected {})",
+                            version, SPECTATOR_PROTOCOL_VERSION
+                        )));
+                    }
+                    config.validate()?;
+
+                    return Ok(Self {
+                        connection,
+                        seed,
+                        player: Player::new(&config),
+                        config: *config,
+                        obstacles: Vec::new(),
+                        pickups: Vec::new(),
+                        tick: 0,
+                        score: 0,
+                        game_over: false,
+                        connected: true,
+                    });
+                }
+                // A frame of a run that started before we joined.
+                Ok(SpectatorMessage::Tick(_)) => {}
+                Err(e) => return Err(connection.error(e)),
+            }
+        }
+    }
+
+    /// Applies every line of the broadcast that has arrived.
+    ///
+    /// Never blocks. Once the broadcast ends the last frame is kept.
+    pub fn poll(&mut self) {
+        while self.connected {
+            match self.connection.try_receive() {
+                Ok(Some(SpectatorMessage::Start { seed, config, .. })) => {
+                    if config.validate().is_ok() {
+                        self.start(seed, *config);
+                    }
+                }
+                Ok(Some(SpectatorMessage::Tick(frame))) => self.apply(frame),
+                Ok(None) => break,
+                Err(_) => self.connected = false,
+            }
+        }
+    }
+
+    fn start(&mut self, seed: u64, config: GameConfig) {
+        self.player = Player::new(&config);
+        self.seed = seed;
+        self.config = config;
+        self.obstacles.clear();
+        self.pickups.clear();
+        self.tick = 0;
+        self.score = 0;
+        self.game_over = false;
+    }
+
+    fn apply(&mut self, frame: TickFrame) {
+        let PlayerFrame { x, y, velocity } = frame.player;
+        self.player.set_motion(Position::new(x, y), velocity);
+        if frame.tick != self.tick {
+            self.player.advance_animation();
+        }
+
+        self.obstacles = frame.obstacles;
+        self.pickups = frame.pickups;
+        self.tick = frame.tick;
+        self.score = frame.score;
+        self.game_over = frame.game_over;
+    }
+
+    /// Address of the broadcasting game.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.connection.peer()
+    }
+
+    /// Seed of the run being watched.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Configuration of the run being watched.
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    pub fn pickups(&self) -> &[Pickup] {
+        &self.pickups
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// Returns `false` once the broadcast has ended.
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
@@ -0,0 +1,79 @@
+//! Timed effects of collected power-ups.
+
+use crate::environment::PickupKind;
+
+/// Power-ups affecting the player, with the ticks each has left.
+///
+/// Collecting a pickup that is already active restarts its timer. The
+/// shield also ends early once it absorbs a collision; the obstacle it
+/// was spent on can then be flown through.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::environment::PickupKind;
+/// use flappycheems::player::ActiveEffects;
+///
+/// let mut effects = ActiveEffects::default();
+/// effects.start(PickupKind::Shield, 2);
+/// assert!(effects.is_active(PickupKind::Shield));
+///
+/// effects.advance();
+/// effects.advance();
+/// assert!(!effects.is_active(PickupKind::Shield));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActiveEffects {
+    remaining: [u32; PickupKind::ALL.len()],
+    /// Column of the obstacle the shield was spent on.
+    shielded_obstacle: Option<i32>,
+}
+
+impl ActiveEffects {
+    /// Starts `kind`, or restarts it, for `ticks` ticks.
+    pub fn start(&mut self, kind: PickupKind, ticks: u32) {
+        self.remaining[kind.index()] = ticks;
+    }
+
+    /// Ticks `kind` has left; zero if it is not active.
+    pub fn remaining(&self, kind: PickupKind) -> u32 {
+        self.remaining[kind.index()]
+    }
+
+    pub fn is_active(&self, kind: PickupKind) -> bool {
+        self.remaining(kind) > 0
+    }
+
+    /// Active effects and their remaining ticks, in [`PickupKind::ALL`] order.
+    pub fn active(&self) -> impl Iterator<Item = (PickupKind, u32)> + '_ {
+        PickupKind::ALL
+            .into_iter()
+            .map(|kind| (kind, self.remaining(kind)))
+            .filter(|&(_, remaining)| remaining > 0)
+    }
+
+    /// Counts one tick off every active effect.
+    pub fn advance(&mut self) {
+        for remaining in &mut self.remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    /// Spends the shield on a collision with the obstacle at column
+    /// `obstacle_x`, returning `false` if there was no shield to spend.
+    pub fn absorb_collision(&mut self, obstacle_x: i32) -> bool {
+        if !self.is_active(PickupKind::Shield) {
+            return false;
+        }
+
+        self.start(PickupKind::Shield, 0);
+        self.shielded_obstacle = Some(obstacle_x);
+        true
+    }
+
+    /// Column of the obstacle the shield was last spent on, which no
+    /// longer counts as a collision.
+    pub fn shielded_obstacle(&self) -> Option<i32> {
+        self.shielded_obstacle
+    }
+}
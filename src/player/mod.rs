@@ -1,3 +1,7 @@
+mod effects;
 mod player_entity;
+mod skin;
 
+pub use effects::ActiveEffects;
 pub use player_entity::{AnimationFrame, Player, Position, Velocity};
+pub use skin::{Skin, SkinRegistry, SpriteRegion};
@@ -5,11 +5,12 @@
 //! - Animation management
 //! - Rendering and visual effects
 //! - Collision detection and boundary checking
+//! - Timed power-up effects
 
-use crate::config::{
-    CHEEMS_ANIMATION_FRAMES, CHEEMS_ANIMATION_FRAME_COUNT, PHYSICS_FLAP_STRENGTH, PHYSICS_GRAVITY,
-    PHYSICS_MAX_VELOCITY, PLAYER_MOVEMENT_SPEED, SCREEN_HEIGHT,
-};
+use crate::config::{GameConfig, PhysicsConfig, PowerUpConfig};
+use crate::environment::{Hitbox, PickupKind};
+use crate::player::{ActiveEffects, Skin};
+use crate::render::{Renderer, SPRITE_LAYER, TEXT_LAYER};
 use bracket_lib::prelude::*;
 
 /// Represents a 2D position in the game world.
@@ -55,13 +56,13 @@ impl Velocity {
         self.value
     }
 
-    pub fn set(&mut self, value: f32) {
-        self.value = value.clamp(-PHYSICS_MAX_VELOCITY * 2.0, PHYSICS_MAX_VELOCITY);
+    pub fn set(&mut self, value: f32, physics: &PhysicsConfig) {
+        self.value = value.clamp(-physics.max_velocity * 2.0, physics.max_velocity);
     }
 
-    pub fn apply_gravity(&mut self) {
-        if self.value < PHYSICS_MAX_VELOCITY {
-            self.value += PHYSICS_GRAVITY;
+    pub fn apply_gravity(&mut self, physics: &PhysicsConfig) {
+        if self.value < physics.max_velocity {
+            self.value += physics.gravity;
         }
     }
 }
@@ -74,31 +75,50 @@ impl Default for Velocity {
 
 /// Manages character animation frame cycling.
 ///
-/// Handles automatic frame advancement and sprite index mapping
-/// for smooth character animation during gameplay.
+/// Plays the frames of a [`Skin`] in order, holding each for the skin's
+/// frame timing, and maps them to sprite sheet indices.
 #[derive(Debug, Clone)]
 pub struct AnimationFrame {
+    /// Sprite indices of the animation sequence.
+    frames: Vec<u16>,
+    /// Ticks each frame stays on screen.
+    frame_ticks: u32,
     /// Current frame index in the animation sequence.
     current: usize,
+    /// Ticks the current frame has been shown for.
+    elapsed: u32,
 }
 
 impl AnimationFrame {
-    pub fn new() -> Self {
-        Self { current: 0 }
+    pub fn new(skin: &Skin) -> Self {
+        Self {
+            frames: skin.sprite_indices(),
+            frame_ticks: skin.frame_ticks.max(1),
+            current: 0,
+            elapsed: 0,
+        }
     }
 
     pub fn advance(&mut self) {
-        self.current = (self.current + 1) % CHEEMS_ANIMATION_FRAME_COUNT;
+        if self.frames.is_empty() {
+            return;
+        }
+
+        self.elapsed += 1;
+        if self.elapsed >= self.frame_ticks {
+            self.elapsed = 0;
+            self.current = (self.current + 1) % self.frames.len();
+        }
     }
 
     pub fn get_sprite_index(&self) -> u16 {
-        CHEEMS_ANIMATION_FRAMES[self.current]
+        self.frames.get(self.current).copied().unwrap_or_default()
     }
 }
 
 impl Default for AnimationFrame {
     fn default() -> Self {
-        Self::new()
+        Self::new(&Skin::default())
     }
 }
 
@@ -110,18 +130,45 @@ impl Default for AnimationFrame {
 pub struct Player {
     /// Current world position.
     position: Position,
+    /// Position before the last physics update.
+    previous: Position,
     /// Current movement velocity.
     velocity: Velocity,
     /// Animation state.
     animation: AnimationFrame,
+    /// Movement and gravity settings.
+    physics: PhysicsConfig,
+    /// Height of the playfield; the player is out of bounds at or below it.
+    screen_height: i32,
+    /// Scale at which the sprite is drawn, in console cells.
+    render_scale: f32,
+    /// Colour of the skin the sprite is multiplied by.
+    tint: RGBA,
+    /// Strength and duration of power-ups.
+    power_ups: PowerUpConfig,
+    /// Power-ups currently affecting the player.
+    effects: ActiveEffects,
 }
 
 impl Player {
-    pub fn new(x: i32, y: i32) -> Self {
+    /// Creates a player at its configured starting position.
+    pub fn new(config: &GameConfig) -> Self {
+        Self::at(config.physics.start_x, config.physics.start_y, config)
+    }
+
+    /// Creates a player at an explicit position.
+    pub fn at(x: i32, y: i32, config: &GameConfig) -> Self {
         Self {
             position: Position::new(x, y as f32),
+            previous: Position::new(x, y as f32),
             velocity: Velocity::default(),
-            animation: AnimationFrame::default(),
+            animation: AnimationFrame::new(&config.skin),
+            physics: config.physics,
+            screen_height: config.display.screen_height,
+            render_scale: config.skin.render_scale,
+            tint: config.skin.tint(),
+            power_ups: config.power_ups,
+            effects: ActiveEffects::default(),
         }
     }
 
@@ -141,39 +188,201 @@ impl Player {
         self.velocity.get()
     }
 
+    /// Places the player at `position` moving at vertical `velocity`,
+    /// keeping its physics settings and animation.
+    pub fn set_motion(&mut self, position: Position, velocity: f32) {
+        self.previous = position.clone();
+        self.position = position;
+        self.velocity = Velocity::new(velocity);
+    }
+
+    /// Columns the player advances per physics update.
+    pub fn movement_speed(&self) -> i32 {
+        self.physics.movement_speed
+    }
+
+    pub fn set_movement_speed(&mut self, speed: i32) {
+        self.physics.movement_speed = speed;
+    }
+
+    pub fn effects(&self) -> &ActiveEffects {
+        &self.effects
+    }
+
+    /// Starts the effect of a collected pickup.
+    pub fn collect(&mut self, kind: PickupKind) {
+        self.effects.start(kind, self.power_ups.duration_ticks);
+    }
+
+    /// Spends the shield, if the player has one, on a collision with the
+    /// obstacle at column `obstacle_x`.
+    pub fn absorb_collision(&mut self, obstacle_x: i32) -> bool {
+        self.effects.absorb_collision(obstacle_x)
+    }
+
+    /// Scale at which the sprite is drawn, in console cells, shrunk while
+    /// the shrink power-up lasts.
+    pub fn scale(&self) -> f32 {
+        self.render_scale * self.shrink_factor()
+    }
+
+    /// Factor the shrink power-up scales the sprite and hitbox by.
+    fn shrink_factor(&self) -> f32 {
+        if self.effects.is_active(PickupKind::Shrink) {
+            self.power_ups.shrink_scale
+        } else {
+            1.0
+        }
+    }
+
+    /// Points each passed obstacle is currently worth.
+    pub fn score_multiplier(&self) -> i32 {
+        if self.effects.is_active(PickupKind::Multiplier) {
+            self.power_ups.score_multiplier
+        } else {
+            1
+        }
+    }
+
+    /// Factor the time between ticks is currently stretched by.
+    pub fn time_scale(&self) -> f32 {
+        if self.effects.is_active(PickupKind::SlowMotion) {
+            self.power_ups.slow_motion_factor
+        } else {
+            1.0
+        }
+    }
+
+    /// Area the player collides with, in world coordinates.
+    ///
+    /// Its size comes from the physics settings, so every character flies
+    /// the same course. Fancy-console glyphs are drawn one row above their
+    /// position and scaled about their centre, so the box is centred half
+    /// a cell right of and half a cell above the player's position.
+    pub fn hitbox(&self) -> Hitbox {
+        self.hitbox_at_scale(self.physics.hitbox_size * self.shrink_factor())
+    }
+
+    /// The hitbox the player has without the shrink power-up.
+    ///
+    /// Obstacles count as passed once this box clears them, so shrinking
+    /// never changes when a point is scored.
+    pub fn full_hitbox(&self) -> Hitbox {
+        self.hitbox_at_scale(self.physics.hitbox_size)
+    }
+
+    /// The hitbox as it was before the last physics update moved it.
+    pub fn previous_hitbox(&self) -> Hitbox {
+        Self::hitbox_at(
+            &self.previous,
+            self.physics.hitbox_size * self.shrink_factor(),
+        )
+    }
+
+    /// Area the hitbox swept through during the last physics update.
+    pub fn swept_hitbox(&self) -> Hitbox {
+        self.previous_hitbox().union(&self.hitbox())
+    }
+
+    fn hitbox_at_scale(&self, scale: f32) -> Hitbox {
+        Self::hitbox_at(&self.position, scale)
+    }
+
+    fn hitbox_at(position: &Position, scale: f32) -> Hitbox {
+        Hitbox::centered(position.x as f32 + 0.5, position.y - 0.5, scale, scale)
+    }
+
     pub fn is_out_of_bounds(&self) -> bool {
-        self.position.y as i32 >= SCREEN_HEIGHT || self.position.y < 0.0
+        self.position.y as i32 >= self.screen_height || self.position.y < 0.0
     }
 
     pub fn update_physics(&mut self) {
-        self.velocity.apply_gravity();
+        self.previous = self.position.clone();
+        self.velocity.apply_gravity(&self.physics);
         self.position.y += self.velocity.get();
-        self.position.x += PLAYER_MOVEMENT_SPEED;
+        self.position.x += self.physics.movement_speed;
 
         if self.position.y < 0.0 {
             self.position.y = 0.0;
-            self.velocity.set(0.0);
+            self.velocity.set(0.0, &self.physics);
         }
 
         self.animation.advance();
+        self.effects.advance();
+    }
+
+    /// Shows the next frame of the animation without moving the player.
+    pub fn advance_animation(&mut self) {
+        self.animation.advance();
     }
 
     pub fn flap(&mut self) {
-        self.velocity.set(PHYSICS_FLAP_STRENGTH);
+        self.velocity.set(self.physics.flap_strength, &self.physics);
     }
 
-    pub fn render(&self, ctx: &mut BTerm) {
-        ctx.set_active_console(1);
+    pub fn render(&self, ctx: &mut dyn Renderer) {
+        ctx.set_active_console(SPRITE_LAYER);
         ctx.cls();
+        ctx.set_active_console(TEXT_LAYER);
+        self.render_tinted(ctx, self.position.x, WHITE.into());
+    }
+
+    /// Draws the player over whatever is already on the sprite layer,
+    /// seen from a camera at world column `camera_x` and tinted `tint`.
+    ///
+    /// Used for other players sharing the screen with the main one. The
+    /// tint is applied on top of the skin's own, and the sprite's
+    /// background takes the tint's alpha, so a translucent tint draws a
+    /// translucent sprite.
+    #[cfg(not(feature = "terminal"))]
+    pub fn render_tinted(&self, ctx: &mut dyn Renderer, camera_x: i32, tint: RGBA) {
+        let tint = self.skin_tinted(tint);
+        let background = RGBA {
+            a: tint.a,
+            ..NAVY.into()
+        };
+
+        ctx.set_active_console(SPRITE_LAYER);
         ctx.set_fancy(
-            PointF::new(0.0, self.position.y),
+            PointF::new((self.position.x - camera_x) as f32, self.position.y),
             1,
             Degrees::new(0.0),
-            PointF::new(2.0, 2.0),
-            WHITE,
-            NAVY,
+            PointF::new(self.scale(), self.scale()),
+            tint,
+            background,
             self.animation.get_sprite_index(),
         );
-        ctx.set_active_console(0);
+        ctx.set_active_console(TEXT_LAYER);
+    }
+
+    /// Draws the player as a glyph on the base console, seen from a camera
+    /// at world column `camera_x` and tinted `tint`.
+    ///
+    /// Terminals have no sprite layer, so the glyph takes the cell the
+    /// sprite's centre would cover, and a translucent tint is blended into
+    /// the background instead.
+    #[cfg(feature = "terminal")]
+    pub fn render_tinted(&self, ctx: &mut dyn Renderer, camera_x: i32, tint: RGBA) {
+        let tint = self.skin_tinted(tint);
+        let background: RGBA = NAVY.into();
+        let color = background.lerp(RGBA { a: 1.0, ..tint }, tint.a);
+
+        ctx.set(
+            self.position.x - camera_x,
+            (self.position.y - 0.5).floor() as i32,
+            color,
+            background,
+            to_cp437(crate::config::PLAYER_TERMINAL_GLYPH),
+        );
+    }
+
+    /// `tint` multiplied by the skin's colour, keeping the tint's alpha.
+    fn skin_tinted(&self, tint: RGBA) -> RGBA {
+        RGBA::from_f32(
+            tint.r * self.tint.r,
+            tint.g * self.tint.g,
+            tint.b * self.tint.b,
+            tint.a,
+        )
     }
 }
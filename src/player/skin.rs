@@ -0,0 +1,304 @@
+//! Characters the player can fly as.
+
+use crate::config::{
+    CHEEMS_ANIMATION_FRAMES, DATA_DIR_NAME, DEFAULT_SKIN_FRAME_TICKS, DEFAULT_SKIN_NAME,
+    PLAYER_RENDER_SCALE, SKINS_FILE_NAME, SPRITE_SHEET_COLUMNS, SPRITE_SHEET_ROWS,
+};
+use crate::error::{GameError, GameResult};
+use bracket_lib::prelude::RGBA;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Characters shipped with the game.
+const BUILTIN_SKINS: &str = include_str!("../../resources/skins.toml");
+
+/// A rectangle of the sprite sheet, in glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpriteRegion {
+    /// Column of the region's left edge.
+    pub x: u16,
+    /// Row of the region's top edge.
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl SpriteRegion {
+    /// Sprite sheet index of the `frame`th glyph of the region, counting
+    /// row by row from its top-left corner.
+    pub fn sprite_index(&self, frame: u16) -> u16 {
+        let width = self.width.max(1);
+        (self.y + frame / width) * SPRITE_SHEET_COLUMNS + self.x + frame % width
+    }
+
+    /// Number of glyphs in the region.
+    pub fn len(&self) -> u32 {
+        u32::from(self.width) * u32::from(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for SpriteRegion {
+    /// The whole sheet, so frames are plain sprite indices.
+    fn default() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: SPRITE_SHEET_COLUMNS,
+            height: SPRITE_SHEET_ROWS,
+        }
+    }
+}
+
+/// How a character looks: where its frames are on the sprite sheet, how
+/// they are played and how the sprite is drawn.
+///
+/// A skin only changes how the player looks: the hitbox is sized by the
+/// physics settings, and replays and network races leave each machine
+/// flying its own character.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::player::{Skin, SpriteRegion};
+///
+/// let skin = Skin {
+///     region: SpriteRegion { x: 1, y: 0, width: 3, height: 1 },
+///     frames: vec![0, 1, 2, 1],
+///     ..Skin::default()
+/// };
+///
+/// assert_eq!(skin.sprite_indices(), vec![1, 2, 3, 2]);
+/// assert!(skin.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Skin {
+    /// Name shown in the characters menu.
+    pub name: String,
+    /// Part of the sprite sheet the frames are taken from.
+    pub region: SpriteRegion,
+    /// Glyphs of the animation within `region`, in order.
+    pub frames: Vec<u16>,
+    /// Ticks each frame stays on screen.
+    pub frame_ticks: u32,
+    /// Scale at which the sprite is drawn, in console cells.
+    pub render_scale: f32,
+    /// Colour the sprite is multiplied by; white leaves it unchanged.
+    pub tint: [u8; 3],
+}
+
+impl Skin {
+    /// Sprite sheet indices of the animation frames, in order.
+    pub fn sprite_indices(&self) -> Vec<u16> {
+        self.frames
+            .iter()
+            .map(|&frame| self.region.sprite_index(frame))
+            .collect()
+    }
+
+    pub fn tint(&self) -> RGBA {
+        let [r, g, b] = self.tint;
+        RGBA::from_u8(r, g, b, 255)
+    }
+
+    /// Checks that the animation can be drawn from the sprite sheet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::InvalidSkin` if the skin has no frames, a frame
+    /// lies outside its region, the region leaves the sheet, frames last
+    /// no ticks or the sprite has no size.
+    pub fn validate(&self) -> GameResult<()> {
+        let invalid = |reason: String| {
+            Err(GameError::InvalidSkin {
+                name: self.name.clone(),
+                reason,
+            })
+        };
+        let region = &self.region;
+
+        if region.is_empty()
+            || u32::from(region.x) + u32::from(region.width) > u32::from(SPRITE_SHEET_COLUMNS)
+            || u32::from(region.y) + u32::from(region.height) > u32::from(SPRITE_SHEET_ROWS)
+        {
+            return invalid(format!(
+                "region {}x{} at ({}, {}) does not fit the {}x{} sprite sheet",
+                region.width,
+                region.height,
+                region.x,
+                region.y,
+                SPRITE_SHEET_COLUMNS,
+                SPRITE_SHEET_ROWS
+            ));
+        }
+        if self.frames.is_empty() {
+            return invalid("no animation frames".to_string());
+        }
+        if let Some(frame) = self
+            .frames
+            .iter()
+            .find(|&&frame| u32::from(frame) >= region.len())
+        {
+            return invalid(format!(
+                "frame {} is outside its {}-glyph region",
+                frame,
+                region.len()
+            ));
+        }
+        if self.frame_ticks == 0 {
+            return invalid("frames must last at least one tick".to_string());
+        }
+        if !self.render_scale.is_finite() || self.render_scale <= 0.0 {
+            return invalid(format!(
+                "render scale {} is not positive",
+                self.render_scale
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Skin {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_SKIN_NAME.to_string(),
+            region: SpriteRegion::default(),
+            frames: CHEEMS_ANIMATION_FRAMES.to_vec(),
+            frame_ticks: DEFAULT_SKIN_FRAME_TICKS,
+            render_scale: PLAYER_RENDER_SCALE,
+            tint: [255, 255, 255],
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SkinFile {
+    skin: Vec<Skin>,
+}
+
+/// The characters offered in the characters menu.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::player::SkinRegistry;
+///
+/// let registry = SkinRegistry::from_toml_str(
+///     r#"
+///     [[skin]]
+///     name = "Blue Cheems"
+///     tint = [120, 160, 255]
+///     "#,
+///     "example",
+/// )?;
+///
+/// let skin = registry.get("Blue Cheems").unwrap();
+/// assert_eq!(skin.frames, SkinRegistry::builtin().skins()[0].frames);
+/// # Ok::<(), flappycheems::error::GameError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkinRegistry {
+    skins: Vec<Skin>,
+}
+
+impl SkinRegistry {
+    /// The characters shipped with the game.
+    pub fn builtin() -> Self {
+        Self::from_toml_str(BUILTIN_SKINS, "built-in characters")
+            .expect("built-in characters are valid")
+    }
+
+    /// Parses a registry from TOML text listing `[[skin]]` tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::InvalidSkinFile` if the text is not valid TOML,
+    /// lists no characters or names one twice, and `GameError::InvalidSkin`
+    /// if a character cannot be drawn. `origin` names the source of the
+    /// text in the error message.
+    pub fn from_toml_str(contents: &str, origin: &str) -> GameResult<Self> {
+        let invalid = |reason: String| GameError::InvalidSkinFile {
+            path: origin.to_string(),
+            reason,
+        };
+
+        let file: SkinFile = toml::from_str(contents).map_err(|e| invalid(e.to_string()))?;
+        if file.skin.is_empty() {
+            return Err(invalid("no characters defined".to_string()));
+        }
+        for (index, skin) in file.skin.iter().enumerate() {
+            skin.validate()?;
+            if file.skin[..index]
+                .iter()
+                .any(|other| other.name == skin.name)
+            {
+                return Err(invalid(format!("{} is defined twice", skin.name)));
+            }
+        }
+
+        Ok(Self { skins: file.skin })
+    }
+
+    /// Loads a registry from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::Io` if the file cannot be read, and the errors
+    /// of [`SkinRegistry::from_toml_str`] if its contents are malformed.
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| GameError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        Self::from_toml_str(&contents, &path.display().to_string())
+    }
+
+    /// Loads the first registry file found in the standard locations, or
+    /// the built-in characters if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a registry file exists but cannot be loaded.
+    pub fn discover() -> GameResult<Self> {
+        match Self::search_paths().into_iter().find(|path| path.is_file()) {
+            Some(path) => Self::load(path),
+            None => Ok(Self::builtin()),
+        }
+    }
+
+    /// Locations searched by [`SkinRegistry::discover`], in priority order.
+    pub fn search_paths() -> Vec<PathBuf> {
+        let user_config =
+            dirs::config_dir().map(|dir| dir.join(DATA_DIR_NAME).join(SKINS_FILE_NAME));
+
+        user_config
+            .into_iter()
+            .chain(Some(PathBuf::from(SKINS_FILE_NAME)))
+            .collect()
+    }
+
+    /// Every character, in menu order.
+    pub fn skins(&self) -> &[Skin] {
+        &self.skins
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Skin> {
+        self.skins.iter().find(|skin| skin.name == name)
+    }
+}
+
+impl Default for SkinRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
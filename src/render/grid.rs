@@ -0,0 +1,229 @@
+use crate::render::Renderer;
+use bracket_lib::prelude::*;
+use std::fmt::Write;
+
+/// A single character cell of a [`GridRenderer`] layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub glyph: FontCharType,
+    pub fg: RGBA,
+    pub bg: RGBA,
+}
+
+impl Cell {
+    /// The cell `cls` leaves behind: a space, white on `bg`.
+    fn blank(bg: RGBA) -> Self {
+        Self {
+            glyph: to_cp437(' '),
+            fg: RGBA::from_u8(255, 255, 255, 255),
+            bg,
+        }
+    }
+}
+
+/// A sprite drawn with [`Renderer::set_fancy`] on a [`GridRenderer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    pub position: PointF,
+    pub z_order: i32,
+    /// Rotation in degrees.
+    pub rotation: f32,
+    pub scale: PointF,
+    pub fg: RGBA,
+    pub bg: RGBA,
+    pub glyph: FontCharType,
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    cells: Vec<Cell>,
+    sprites: Vec<Sprite>,
+}
+
+/// A renderer drawing into memory instead of a window.
+///
+/// Follows the semantics of bracket-lib's simple consoles, so a screen
+/// drawn here matches the one the game shows cell for cell. Layers are
+/// created the first time they are made active; sprites are recorded per
+/// layer rather than rasterised.
+///
+/// # Examples
+///
+/// ```
+/// use flappycheems::render::{GridRenderer, Renderer};
+///
+/// let mut screen = GridRenderer::new(12, 3);
+/// screen.cls();
+/// screen.print_centered(1, "Cheems");
+///
+/// assert_eq!(screen.row_text(1), "   Cheems   ");
+/// assert_eq!(screen.text(), "\n   Cheems\n\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridRenderer {
+    width: u32,
+    height: u32,
+    layers: Vec<Layer>,
+    active: usize,
+}
+
+impl GridRenderer {
+    /// Creates a blank screen of `width` by `height` cells.
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut grid = Self {
+            width,
+            height,
+            layers: Vec::new(),
+            active: 0,
+        };
+        grid.set_active_console(0);
+        grid
+    }
+
+    /// The cell at `x`, `y` of `layer`, if both exist.
+    pub fn cell(&self, layer: usize, x: i32, y: i32) -> Option<&Cell> {
+        let index = self.index(x, y)?;
+        self.layers.get(layer).map(|layer| &layer.cells[index])
+    }
+
+    /// Sprites drawn on `layer` since it was last cleared, in drawing order.
+    pub fn sprites(&self, layer: usize) -> &[Sprite] {
+        self.layers
+            .get(layer)
+            .map_or(&[], |layer| layer.sprites.as_slice())
+    }
+
+    /// The glyphs of row `y` of the base layer as text.
+    pub fn row_text(&self, y: i32) -> String {
+        (0..self.width as i32)
+            .filter_map(|x| self.cell(0, x, y))
+            .map(|cell| to_char(cell.glyph as u8))
+            .collect()
+    }
+
+    /// The glyphs of the base layer as text, one line per row, without
+    /// trailing spaces.
+    pub fn text(&self) -> String {
+        (0..self.height as i32)
+            .map(|y| self.row_text(y).trim_end().to_string() + "\n")
+            .collect()
+    }
+
+    /// A plain-text picture of the whole screen: the base layer's glyphs,
+    /// followed by one line per sprite on any layer.
+    ///
+    /// Meant to be compared against golden files.
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = self.text();
+        for (index, layer) in self.layers.iter().enumerate() {
+            for sprite in &layer.sprites {
+                let to_u8 = |channel: f32| (channel * 255.0).round() as u8;
+                let _ = writeln!(
+                    snapshot,
+                    "sprite {} on layer {} at ({:.2}, {:.2}) scale {:.2} tint #{:02x}{:02x}{:02x}{:02x}",
+                    sprite.glyph,
+                    index,
+                    sprite.position.x,
+                    sprite.position.y,
+                    sprite.scale.x,
+                    to_u8(sprite.fg.r),
+                    to_u8(sprite.fg.g),
+                    to_u8(sprite.fg.b),
+                    to_u8(sprite.fg.a),
+                );
+            }
+        }
+        snapshot
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let inside = (0..self.width as i32).contains(&x) && (0..self.height as i32).contains(&y);
+        inside.then(|| (y * self.width as i32 + x) as usize)
+    }
+
+    fn layer(&mut self) -> &mut Layer {
+        &mut self.layers[self.active]
+    }
+
+    fn write(&mut self, x: i32, y: i32, text: &str, colors: Option<(RGBA, RGBA)>) {
+        for (x, glyph) in (x..).zip(string_to_cp437(text)) {
+            let Some(index) = self.index(x, y) else {
+                continue;
+            };
+            let cell = &mut self.layer().cells[index];
+            cell.glyph = glyph;
+            if let Some((fg, bg)) = colors {
+                cell.fg = fg;
+                cell.bg = bg;
+            }
+        }
+    }
+}
+
+impl Renderer for GridRenderer {
+    fn get_char_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn set_active_console(&mut self, layer: usize) {
+        let cells = (self.width * self.height) as usize;
+        while self.layers.len() <= layer {
+            self.layers.push(Layer {
+                cells: vec![Cell::blank(RGBA::from_u8(0, 0, 0, 255)); cells],
+                sprites: Vec::new(),
+            });
+        }
+        self.active = layer;
+    }
+
+    fn cls(&mut self) {
+        self.cls_bg(RGBA::from_u8(0, 0, 0, 255));
+    }
+
+    fn cls_bg(&mut self, background: RGBA) {
+        let layer = self.layer();
+        layer.cells.fill(Cell::blank(background));
+        layer.sprites.clear();
+    }
+
+    fn set(&mut self, x: i32, y: i32, fg: RGBA, bg: RGBA, glyph: FontCharType) {
+        if let Some(index) = self.index(x, y) {
+            self.layer().cells[index] = Cell { glyph, fg, bg };
+        }
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        self.write(x, y, text, None);
+    }
+
+    fn print_color(&mut self, x: i32, y: i32, fg: RGBA, bg: RGBA, text: &str) {
+        self.write(x, y, text, Some((fg, bg)));
+    }
+
+    fn print_centered(&mut self, y: i32, text: &str) {
+        // bracket-lib centres on the byte length, not the character count.
+        let x = self.width as i32 / 2 - text.len() as i32 / 2;
+        self.print(x, y, text);
+    }
+
+    fn set_fancy(
+        &mut self,
+        position: PointF,
+        z_order: i32,
+        rotation: Degrees,
+        scale: PointF,
+        fg: RGBA,
+        bg: RGBA,
+        glyph: FontCharType,
+    ) {
+        self.layer().sprites.push(Sprite {
+            position,
+            z_order,
+            rotation: rotation.0,
+            scale,
+            fg,
+            bg,
+            glyph,
+        });
+    }
+}
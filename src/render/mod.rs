@@ -0,0 +1,14 @@
+//! Drawing the game on a screen.
+//!
+//! Everything the game draws goes through the [`Renderer`] trait, which
+//! covers the handful of console operations it uses. [`BTerm`] implements
+//! it for the real window or terminal, and [`GridRenderer`] draws into
+//! memory so tests can compare whole screens against golden files.
+//!
+//! [`BTerm`]: bracket_lib::prelude::BTerm
+
+mod grid;
+mod renderer;
+
+pub use grid::{Cell, GridRenderer, Sprite};
+pub use renderer::{Renderer, SPRITE_LAYER, TEXT_LAYER};
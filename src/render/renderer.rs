@@ -0,0 +1,98 @@
+use bracket_lib::prelude::*;
+
+/// Console holding the background, obstacles and interface text.
+pub const TEXT_LAYER: usize = 0;
+
+/// Console holding the player sprites.
+pub const SPRITE_LAYER: usize = 1;
+
+/// A screen the game can draw on.
+///
+/// The methods mirror the `BTerm` operations of the same name. Like
+/// `BTerm`, a renderer draws on one console layer at a time, chosen with
+/// [`set_active_console`](Renderer::set_active_console).
+pub trait Renderer {
+    /// Width and height of the active console, in cells.
+    fn get_char_size(&self) -> (u32, u32);
+
+    /// Makes `layer` the console later calls draw on.
+    fn set_active_console(&mut self, layer: usize);
+
+    /// Clears the active console to blank cells on a black background.
+    fn cls(&mut self);
+
+    /// Clears the active console to blank cells on `background`.
+    fn cls_bg(&mut self, background: RGBA);
+
+    /// Sets the cell at `x`, `y` to `glyph` in the given colours.
+    fn set(&mut self, x: i32, y: i32, fg: RGBA, bg: RGBA, glyph: FontCharType);
+
+    /// Writes `text` from `x`, `y`, keeping the colours of the cells.
+    fn print(&mut self, x: i32, y: i32, text: &str);
+
+    /// Writes `text` from `x`, `y` in the given colours.
+    fn print_color(&mut self, x: i32, y: i32, fg: RGBA, bg: RGBA, text: &str);
+
+    /// Writes `text` centred on row `y`.
+    fn print_centered(&mut self, y: i32, text: &str);
+
+    /// Draws `glyph` as a free-floating sprite centred on `position`.
+    #[allow(clippy::too_many_arguments)]
+    fn set_fancy(
+        &mut self,
+        position: PointF,
+        z_order: i32,
+        rotation: Degrees,
+        scale: PointF,
+        fg: RGBA,
+        bg: RGBA,
+        glyph: FontCharType,
+    );
+}
+
+impl Renderer for BTerm {
+    fn get_char_size(&self) -> (u32, u32) {
+        BTerm::get_char_size(self)
+    }
+
+    fn set_active_console(&mut self, layer: usize) {
+        BTerm::set_active_console(self, layer);
+    }
+
+    fn cls(&mut self) {
+        BTerm::cls(self);
+    }
+
+    fn cls_bg(&mut self, background: RGBA) {
+        BTerm::cls_bg(self, background);
+    }
+
+    fn set(&mut self, x: i32, y: i32, fg: RGBA, bg: RGBA, glyph: FontCharType) {
+        BTerm::set(self, x, y, fg, bg, glyph);
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        BTerm::print(self, x, y, text);
+    }
+
+    fn print_color(&mut self, x: i32, y: i32, fg: RGBA, bg: RGBA, text: &str) {
+        BTerm::print_color(self, x, y, fg, bg, text);
+    }
+
+    fn print_centered(&mut self, y: i32, text: &str) {
+        BTerm::print_centered(self, y, text);
+    }
+
+    fn set_fancy(
+        &mut self,
+        position: PointF,
+        z_order: i32,
+        rotation: Degrees,
+        scale: PointF,
+        fg: RGBA,
+        bg: RGBA,
+        glyph: FontCharType,
+    ) {
+        BTerm::set_fancy(self, position, z_order, rotation, scale, fg, bg, glyph);
+    }
+}
@@ -0,0 +1,40 @@
+//! The lookahead bot is the regression oracle for physics changes: if it
+//! can no longer get through a course, the course or the physics broke.
+
+use flappycheems::agent::{Agent, LookaheadAgent, Observation};
+use flappycheems::config::GameConfig;
+use flappycheems::game::{DifficultyPreset, Simulation};
+
+/// Obstacles the bot must get past on every course.
+const OBSTACLES: i32 = 15;
+
+/// Courses played per difficulty preset.
+const SEEDS: u64 = 3;
+
+#[test]
+fn lookahead_bot_gets_through_every_course() {
+    let mut agent = LookaheadAgent::new();
+
+    for preset in DifficultyPreset::ALL {
+        let mut config = GameConfig::default();
+        config.difficulty.preset = preset;
+
+        for seed in 0..SEEDS {
+            let mut simulation = Simulation::with_config(config.clone(), seed);
+            agent.reset();
+
+            while simulation.obstacles_passed() < OBSTACLES && !simulation.is_game_over() {
+                let input = agent.act(&Observation::of(&simulation));
+                simulation.step(input);
+            }
+
+            assert!(
+                !simulation.is_game_over(),
+                "bot crashed after {} obstacles on seed {} on the {} preset",
+                simulation.obstacles_passed(),
+                seed,
+                preset
+            );
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use flappycheems::config::{GameConfig, VEC_ENV_MIN_ENVS_PER_THREAD};
+use flappycheems::game::TickInput;
+use flappycheems::gym::{Env, VecEnv};
+
+const ENVS: usize = 6;
+
+/// Enough environments for the batch to be split across threads, into
+/// two full chunks and a remainder.
+const THREADED_ENVS: usize = 2 * VEC_ENV_MIN_ENVS_PER_THREAD + 1;
+const TICKS: u64 = 300;
+
+/// Flaps whenever the player is below the bottom of the next obstacle's
+/// first opening.
+fn policy(features: &[f32]) -> TickInput {
+    TickInput {
+        flap: features[0] > features[5] + 0.05,
+    }
+}
+
+/// Steps `count` environments together, on up to `max_threads` threads,
+/// and one by one, in order, with the same seeds and actions, and checks
+/// that every step agrees.
+fn assert_matches_single_environments(count: usize, max_threads: usize) -> VecEnv {
+    let config = GameConfig::default();
+    let seeds: Vec<u64> = (0..count as u64).map(|seed| seed * 7 + 1).collect();
+
+    let mut envs = VecEnv::new(config.clone(), count)
+        .unwrap()
+        .with_max_ticks(TICKS)
+        .with_max_threads(max_threads);
+    let mut singles: Vec<Env> = (0..count)
+        .map(|_| Env::new(config.clone()).unwrap().with_max_ticks(TICKS))
+        .collect();
+
+    let mut observations = envs.reset(&seeds);
+    for (index, env) in singles.iter_mut().enumerate() {
+        assert_eq!(observations[index], env.reset(seeds[index]));
+    }
+
+    while !envs.all_done() {
+        let actions: Vec<TickInput> = observations.iter().map(|obs| policy(obs)).collect();
+        let step = envs.step(&actions);
+
+        for (index, env) in singles.iter_mut().enumerate() {
+            let (observation, reward, done, info) = env.step(actions[index]);
+            assert_eq!(step.observations[index], observation);
+            assert_eq!(step.rewards[index], reward);
+            assert_eq!(step.dones[index], done);
+            assert_eq!(step.infos[index], info);
+        }
+        observations = step.observations;
+    }
+
+    envs
+}
+
+#[test]
+fn vectorised_steps_match_single_environments() {
+    let envs = assert_matches_single_environments(ENVS, 1);
+
+    for env in envs.envs() {
+        assert!(env.simulation().is_game_over() || env.simulation().tick() == TICKS);
+    }
+}
+
+#[test]
+fn threaded_steps_match_single_environments_in_order() {
+    // Split across threads even on a single-core machine.
+    let envs = assert_matches_single_environments(THREADED_ENVS, THREADED_ENVS);
+    assert_eq!(envs.len(), THREADED_ENVS);
+}
@@ -0,0 +1,184 @@
+use flappycheems::agent::{Agent, LookaheadAgent, Observation};
+use flappycheems::config::GameConfig;
+use flappycheems::game::{Simulation, TickInput};
+use flappycheems::net::{
+    Message, NET_PROTOCOL_VERSION, NetSession, PeerStatus, SpectatorClient, SpectatorMessage,
+    SpectatorServer, TickFrame,
+};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SEED: u64 = 21;
+const TICKS: u64 = 200;
+const WAIT: Duration = Duration::from_secs(5);
+
+/// Opens a lobby on loopback and joins it, returning the host and guest.
+fn connect(config: GameConfig, seed: u64) -> (NetSession, NetSession) {
+    let listener = NetSession::listen("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || NetSession::accept(&listener, config, seed));
+
+    let guest = NetSession::join(addr).unwrap();
+    (host.join().unwrap().unwrap(), guest)
+}
+
+/// Polls `session` until `done` holds, failing after a few seconds.
+fn poll_until(session: &mut NetSession, done: impl Fn(&NetSession) -> bool) {
+    let start = Instant::now();
+    while !done(session) {
+        assert!(start.elapsed() < WAIT, "peer did not answer in time");
+        session.poll();
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn rivals_follow_each_other_over_loopback() {
+    let (mut host, mut guest) = connect(GameConfig::default(), SEED);
+    assert_eq!(guest.seed(), SEED);
+    assert_eq!(guest.config(), host.config());
+
+    let mut host_run = Simulation::with_config(host.config().clone(), SEED);
+    let mut guest_run = Simulation::with_config(guest.config().clone(), SEED);
+    let mut bot = LookaheadAgent::new();
+
+    for tick in 0..TICKS {
+        if !host_run.is_game_over() {
+            let input = bot.act(&Observation::of(&host_run));
+            host_run.step(input);
+            host.send_tick(tick, input, &host_run);
+        }
+        if !guest_run.is_game_over() {
+            let input = TickInput {
+                flap: tick % 6 == 0,
+            };
+            guest_run.step(input);
+            guest.send_tick(tick, input, &guest_run);
+        }
+    }
+
+    poll_until(&mut host, |host| host.remote().tick() == guest_run.tick());
+    poll_until(&mut guest, |guest| guest.remote().tick() == host_run.tick());
+
+    assert_eq!(host.status(), PeerStatus::Connected);
+    assert_eq!(guest.status(), PeerStatus::Connected);
+    assert_eq!(host.remote().state_hash(), guest_run.state_hash());
+    assert_eq!(guest.remote().state_hash(), host_run.state_hash());
+    assert_eq!(guest.remote().score(), host_run.score());
+}
+
+#[test]
+fn misreported_input_is_detected_as_desync() {
+    let (mut host, mut guest) = connect(GameConfig::default(), SEED);
+    let mut run = Simulation::with_config(host.config().clone(), SEED);
+
+    run.step(TickInput::IDLE);
+    host.send_tick(0, TickInput::IDLE, &run);
+    run.step(TickInput::FLAP);
+    host.send_tick(1, TickInput::IDLE, &run);
+
+    poll_until(&mut guest, |guest| guest.status() != PeerStatus::Connected);
+    assert_eq!(guest.status(), PeerStatus::Desynced { tick: 1 });
+}
+
+#[test]
+fn leaving_the_race_disconnects_the_rival() {
+    let (host, mut guest) = connect(GameConfig::default(), SEED);
+    drop(host);
+
+    poll_until(&mut guest, |guest| guest.status() != PeerStatus::Connected);
+    assert_eq!(guest.status(), PeerStatus::Disconnected);
+    assert!(!guest.is_rival_racing());
+}
+
+#[test]
+fn host_rejects_other_protocol_versions() {
+    let listener = NetSession::listen("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || NetSession::accept(&listener, GameConfig::default(), SEED));
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let hello = Message::Hello {
+        version: NET_PROTOCOL_VERSION + 1,
+    };
+    writeln!(stream, "{}", serde_json::to_string(&hello).unwrap()).unwrap();
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line).unwrap();
+    let answer: Message = serde_json::from_str(&line).unwrap();
+
+    assert!(matches!(answer, Message::Reject { .. }));
+    assert!(host.join().unwrap().is_err());
+}
+
+#[test]
+fn spectators_receive_a_json_line_per_tick() {
+    let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let mut run = Simulation::with_seed(SEED);
+
+    // Reads until the server hangs up, so the server never fills the socket.
+    let reader = thread::spawn(move || {
+        let stream = TcpStream::connect(addr).unwrap();
+        BufReader::new(stream)
+            .lines()
+            .map(|line| line.unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    let start = Instant::now();
+    while server.spectator_count() == 0 {
+        assert!(start.elapsed() < WAIT, "spectator was not accepted");
+        server.poll(&run);
+    }
+    for tick in 0..TICKS {
+        run.step(TickInput {
+            flap: tick % 5 == 0,
+        });
+        server.publish(&run);
+    }
+    drop(server);
+
+    let lines = reader.join().unwrap();
+    assert!(matches!(
+        serde_json::from_str(&lines[0]).unwrap(),
+        SpectatorMessage::Start { seed: SEED, .. }
+    ));
+
+    let frames: Vec<TickFrame> = lines[1..]
+        .iter()
+        .map(|line| match serde_json::from_str(line).unwrap() {
+            SpectatorMessage::Tick(frame) => frame,
+            message => panic!("unexpected {:?}", message),
+        })
+        .collect();
+    // One frame on joining, then one per published tick; a crashed run
+    // stops counting ticks.
+    assert_eq!(frames.len() as u64, TICKS + 1);
+    assert!(
+        frames
+            .iter()
+            .zip(0..)
+            .all(|(frame, tick)| frame.tick == u64::min(tick, run.tick()))
+    );
+}
+
+#[test]
+fn spectators_can_join_before_any_run_is_published() {
+    let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let idle = Simulation::with_seed(SEED);
+
+    let client = thread::spawn(move || SpectatorClient::connect(addr));
+    let start = Instant::now();
+    while !client.is_finished() {
+        assert!(start.elapsed() < WAIT, "spectator was not greeted");
+        server.poll(&idle);
+    }
+
+    let client = client.join().unwrap().unwrap();
+    assert_eq!(client.seed(), SEED);
+    assert_eq!(server.spectator_count(), 1);
+}
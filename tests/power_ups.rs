@@ -0,0 +1,221 @@
+//! Collected power-ups must change the run the way they promise.
+
+use flappycheems::config::GameConfig;
+use flappycheems::environment::{Obstacle, ObstacleGap, PickupKind};
+use flappycheems::game::{GameState, Seat, Simulation, TickInput};
+use flappycheems::player::Player;
+
+/// Courses searched for a run that collects the power-up under test.
+const SEEDS: u64 = 32;
+
+/// Ticks flown on a course before giving up on it.
+const MAX_TICKS: u64 = 2_000;
+
+/// A configuration that puts a pickup in every obstacle's gap.
+fn config() -> GameConfig {
+    let mut config = GameConfig::default();
+    config.power_ups.spawn_chance = 100;
+    config
+}
+
+/// Row the player aims for: the pickup waiting in the next obstacle's
+/// gap, or else the middle of the gap.
+///
+/// The hitbox reaches a row and a half above the player's position and
+/// half a row below it, so aiming one row below a cell centres the
+/// hitbox on it.
+fn target_row(simulation: &Simulation) -> f32 {
+    let Some(obstacle) = simulation.next_obstacle() else {
+        return simulation.config().display.screen_height as f32 / 2.0;
+    };
+    let row = simulation
+        .pickups()
+        .iter()
+        .find(|pickup| pickup.x() == obstacle.x())
+        .map_or(obstacle.gap().center_y(), |pickup| pickup.y());
+
+    row as f32 + 1.0
+}
+
+/// Hovers just above `row`, flapping whenever the player falls below it.
+fn hover(simulation: &Simulation, row: f32) -> TickInput {
+    let player = simulation.player();
+    TickInput {
+        flap: player.y() > row && player.velocity() >= 0.0,
+    }
+}
+
+/// Flies the player one tick towards the next pickup or gap.
+fn fly_through_gaps(simulation: &mut Simulation) {
+    let input = hover(simulation, target_row(simulation));
+    simulation.step(input);
+}
+
+/// The first run, searching the courses in order, that collects `kind`
+/// by flying through gaps, stopped on the tick it is collected.
+fn collect(kind: PickupKind) -> Simulation {
+    (0..SEEDS)
+        .find_map(|seed| {
+            let mut simulation = Simulation::with_config(config(), seed);
+            while !simulation.is_game_over() && simulation.tick() < MAX_TICKS {
+                fly_through_gaps(&mut simulation);
+                if simulation.player().effects().is_active(kind) {
+                    return Some(simulation);
+                }
+            }
+            None
+        })
+        .unwrap_or_else(|| panic!("no course up to seed {} yields a {}", SEEDS, kind))
+}
+
+/// Flies the player one tick towards the middle of a pillar of the next
+/// obstacle.
+fn fly_into_pillars(simulation: &mut Simulation) {
+    let height = simulation.config().display.screen_height as f32;
+    let row = simulation
+        .next_obstacle()
+        .into_iter()
+        .flat_map(|obstacle| obstacle.pillar_hitboxes())
+        .map(|pillar| (pillar.top().max(1.0), pillar.bottom().min(height - 1.0)))
+        .find(|(top, bottom)| bottom - top >= 3.0)
+        .map_or(height / 2.0, |(top, bottom)| (top + bottom) / 2.0 + 0.5);
+
+    let input = hover(simulation, row);
+    simulation.step(input);
+}
+
+#[test]
+fn a_shield_absorbs_exactly_one_hit() {
+    let mut simulation = collect(PickupKind::Shield);
+    let mut absorbed_after = None;
+
+    while !simulation.is_game_over() && simulation.tick() < MAX_TICKS {
+        fly_into_pillars(&mut simulation);
+        let effects = simulation.player().effects();
+        if absorbed_after.is_none() && effects.shielded_obstacle().is_some() {
+            assert!(
+                !simulation.is_game_over(),
+                "the shield did not absorb the hit"
+            );
+            absorbed_after = Some(simulation.obstacles_passed());
+        }
+    }
+
+    let absorbed_after = absorbed_after.expect("the player never hit an obstacle");
+    assert!(
+        simulation.is_game_over(),
+        "a second hit did not end the run"
+    );
+    assert!(
+        simulation.obstacles_passed() > absorbed_after,
+        "the run ended on the obstacle the shield absorbed"
+    );
+    assert!(!simulation.player().effects().is_active(PickupKind::Shield));
+}
+
+#[test]
+fn shrinking_makes_the_hitbox_smaller_until_it_wears_off() {
+    let mut simulation = collect(PickupKind::Shrink);
+    let config = simulation.config().clone();
+
+    let player = simulation.player();
+    assert!(player.hitbox().width() < player.full_hitbox().width());
+    assert_eq!(
+        player.hitbox().width(),
+        config.physics.hitbox_size * config.power_ups.shrink_scale
+    );
+    assert_eq!(player.full_hitbox().width(), config.physics.hitbox_size);
+
+    while simulation.player().effects().is_active(PickupKind::Shrink) {
+        fly_through_gaps(&mut simulation);
+        assert!(!simulation.is_game_over(), "crashed while shrunk");
+    }
+    let player = simulation.player();
+    assert_eq!(player.hitbox().width(), config.physics.hitbox_size);
+}
+
+#[test]
+fn a_shrunk_player_cannot_skip_over_a_thin_pillar() {
+    let config = GameConfig::default();
+    assert_eq!(config.obstacle.width, 1);
+    let pillar = Obstacle::with_gap(12, ObstacleGap::new(20, 2), &config.obstacle);
+    let mut player = Player::at(5, 10, &config);
+    player.collect(PickupKind::Shrink);
+    player.set_movement_speed(2);
+    assert_eq!(player.hitbox().width(), 1.0);
+
+    let mut crashed = false;
+    while player.x() <= pillar.right() && !crashed {
+        if player.y() > 10.0 && player.velocity() >= 0.0 {
+            player.flap();
+        }
+        player.update_physics();
+
+        // Every tick ends on an odd column, beside the pillar at 12.
+        let hitbox = player.hitbox();
+        assert!(
+            !pillar
+                .pillar_hitboxes()
+                .iter()
+                .any(|segment| segment.overlaps(&hitbox))
+        );
+        crashed = pillar.has_collision_with(&player);
+    }
+
+    assert!(crashed, "the player jumped over the pillar");
+}
+
+#[test]
+fn the_score_multiplier_multiplies_the_points_of_passed_obstacles() {
+    let mut simulation = collect(PickupKind::Multiplier);
+    let multiplier = simulation.config().power_ups.score_multiplier;
+    let score = simulation.score();
+    let passed = simulation.obstacles_passed();
+
+    while simulation.obstacles_passed() == passed {
+        fly_through_gaps(&mut simulation);
+        assert!(!simulation.is_game_over(), "crashed before scoring");
+    }
+
+    assert!(
+        simulation
+            .player()
+            .effects()
+            .is_active(PickupKind::Multiplier)
+    );
+    assert_eq!(simulation.player().score_multiplier(), multiplier);
+    assert_eq!(
+        simulation.score() - score,
+        multiplier * (simulation.obstacles_passed() - passed)
+    );
+}
+
+#[test]
+fn slow_motion_stretches_the_tick_rate_for_both_versus_players() {
+    let factor = config().power_ups.slow_motion_factor;
+    let slowed = collect(PickupKind::SlowMotion);
+    assert_eq!(slowed.player().time_scale(), factor);
+
+    // Player one falls and crashes while player two flies on and collects
+    // slow motion, which must still slow the shared clock.
+    let mut state = GameState::with_config(config(), Some(slowed.seed()));
+    state.start_versus();
+    assert_eq!(state.time_scale(), 1.0);
+
+    let is_slowed = |state: &GameState| {
+        state
+            .second_player()
+            .is_some_and(|second| second.player().time_scale() > 1.0)
+    };
+    while !is_slowed(&state) {
+        let second = state.second_player().expect("versus run has player two");
+        assert!(!second.is_game_over(), "player two crashed");
+        if hover(second, target_row(second)).flap {
+            state.queue_flap_for(Seat::Two);
+        }
+        state.step();
+    }
+
+    assert!(state.simulation().is_game_over());
+    assert_eq!(state.time_scale(), factor);
+}
@@ -0,0 +1,133 @@
+//! Every generated course must be survivable by a perfect player.
+
+use flappycheems::config::GameConfig;
+use flappycheems::environment::Obstacle;
+use flappycheems::game::{DifficultyPreset, PlayMode, Simulation, TickInput};
+use flappycheems::player::Player;
+
+/// Obstacles a perfect player must get past on every course.
+const OBSTACLES: i32 = 25;
+
+/// Courses checked per difficulty preset.
+const SEEDS: u64 = 8;
+
+/// A course as laid out tick by tick, independent of the player's input.
+struct Course {
+    /// Obstacles in the world after each tick, starting with tick zero.
+    obstacles: Vec<Vec<Obstacle>>,
+    /// Player scroll speed after each tick.
+    speeds: Vec<i32>,
+}
+
+/// Lays out the course of `seed` by playing it in practice mode, where
+/// obstacles are harmless, until `target` obstacles have been passed.
+fn lay_out_course(config: &GameConfig, seed: u64, target: i32) -> Course {
+    let config = GameConfig {
+        mode: PlayMode::Practice,
+        ..config.clone()
+    };
+    let hover_y = config.display.screen_height as f32 / 2.0;
+    let mut simulation = Simulation::with_config(config, seed);
+    let mut course = Course {
+        obstacles: Vec::new(),
+        speeds: Vec::new(),
+    };
+
+    loop {
+        course
+            .obstacles
+            .push(simulation.obstacles().iter().cloned().collect());
+        course.speeds.push(simulation.player().movement_speed());
+        if simulation.obstacles_passed() >= target {
+            return course;
+        }
+
+        let player = simulation.player();
+        let flap = player.y() > hover_y && player.velocity() >= 0.0;
+        simulation.step(TickInput { flap });
+        assert!(!simulation.is_game_over(), "hovering left the screen");
+    }
+}
+
+/// Searches every reachable player state breadth-first for flaps that
+/// get through `course`.
+fn find_perfect_run(config: &GameConfig, course: &Course) -> Option<Vec<TickInput>> {
+    let mut player = Player::new(config);
+    player.set_movement_speed(course.speeds[0]);
+
+    let mut layers: Vec<Vec<(usize, bool)>> = Vec::new();
+    let mut states = vec![player];
+
+    for tick in 1..course.obstacles.len() {
+        let mut parents = Vec::new();
+        let mut next: Vec<Player> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+
+        for (index, state) in states.iter().enumerate() {
+            for flap in [false, true] {
+                let mut player = state.clone();
+                if flap {
+                    player.flap();
+                }
+                player.update_physics();
+                player.set_movement_speed(course.speeds[tick]);
+
+                let crashed = player.is_out_of_bounds()
+                    || course.obstacles[tick]
+                        .iter()
+                        .any(|obstacle| obstacle.has_collision_with(&player));
+                let key = (
+                    (player.y() * 1000.0).round() as i64,
+                    (player.velocity() * 1000.0).round() as i64,
+                );
+                if !crashed && seen.insert(key) {
+                    parents.push((index, flap));
+                    next.push(player);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            return None;
+        }
+        layers.push(parents);
+        states = next;
+    }
+
+    let mut inputs = Vec::with_capacity(layers.len());
+    let mut index = 0;
+    for parents in layers.iter().rev() {
+        let (parent, flap) = parents[index];
+        inputs.push(TickInput { flap });
+        index = parent;
+    }
+    inputs.reverse();
+    Some(inputs)
+}
+
+#[test]
+fn every_course_is_survivable_by_a_perfect_player() {
+    for preset in DifficultyPreset::ALL {
+        let mut config = GameConfig::default();
+        config.difficulty.preset = preset;
+
+        for seed in 0..SEEDS {
+            let course = lay_out_course(&config, seed, OBSTACLES);
+            let inputs = find_perfect_run(&config, &course)
+                .unwrap_or_else(|| panic!("no way through seed {} on the {} preset", seed, preset));
+
+            let mut simulation = Simulation::with_config(config.clone(), seed);
+            for input in inputs {
+                simulation.step(input);
+            }
+
+            assert!(
+                !simulation.is_game_over(),
+                "perfect run crashed on seed {} on the {} preset",
+                seed,
+                preset
+            );
+            assert!(simulation.obstacles_passed() >= OBSTACLES);
+        }
+    }
+}
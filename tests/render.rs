@@ -0,0 +1,133 @@
+//! Golden-file tests of whole screens, drawn into memory.
+//!
+//! Each test draws a screen on a [`GridRenderer`] and compares its snapshot
+//! with the file of the same name in `tests/golden`. After an intended
+//! change to what the game draws, rerun with `UPDATE_GOLDEN=1` to rewrite
+//! the files, and review the diff.
+
+// Terminal builds draw the player as a glyph and label other keys.
+#![cfg(not(feature = "terminal"))]
+
+use flappycheems::agent::{Agent, LookaheadAgent, Observation};
+use flappycheems::game::{
+    GameMode, GameState, TickOutcome, render_characters_menu, render_game_frame,
+    render_game_over_menu, render_high_scores_menu, render_main_menu,
+};
+use flappycheems::render::GridRenderer;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SEED: u64 = 21;
+
+/// Points scored before the game-over run stops flapping.
+const GAME_OVER_SCORE: i32 = 3;
+
+/// Ticks flown before the mid-game frame is drawn.
+const MID_GAME_TICKS: u64 = 120;
+
+/// Screen of the size the game opens its window at.
+fn screen(state: &GameState) -> GridRenderer {
+    let display = &state.config().display;
+    GridRenderer::new(display.screen_width as u32, display.screen_height as u32)
+}
+
+fn assert_golden(name: &str, screen: &GridRenderer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.txt", name));
+    let actual = screen.snapshot();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+    assert!(
+        actual == expected,
+        "screen differs from {}; rerun with UPDATE_GOLDEN=1 if intended\n\
+         --- expected\n{}\n--- actual\n{}",
+        path.display(),
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn main_menu_matches_golden_file() {
+    let state = GameState::with_seed(SEED);
+    let mut screen = screen(&state);
+
+    render_main_menu(&state, &mut screen);
+    assert_golden("main_menu", &screen);
+}
+
+#[test]
+fn game_over_menu_matches_golden_file() {
+    let mut state = GameState::with_seed(SEED);
+    let mut bot = LookaheadAgent::new();
+    state.reset_game();
+    loop {
+        if state.score() < GAME_OVER_SCORE && bot.act(&Observation::of(state.simulation())).flap {
+            state.queue_flap();
+        }
+        if state.step() == TickOutcome::GameOver {
+            break;
+        }
+    }
+    for c in "CHEEMS".chars() {
+        state.push_name_char(c);
+    }
+    state.submit_high_score();
+
+    let mut screen = screen(&state);
+    render_game_over_menu(&state, &mut screen);
+    assert_golden("game_over", &screen);
+}
+
+#[test]
+fn mid_game_frame_matches_golden_file() {
+    let mut state = GameState::with_seed(SEED);
+    state.set_autopilot(Box::new(LookaheadAgent::new()));
+    state.reset_game();
+    for _ in 0..MID_GAME_TICKS {
+        assert_ne!(state.step(), TickOutcome::GameOver);
+    }
+
+    let mut screen = screen(&state);
+    render_game_frame(&state, &mut screen);
+    assert_golden("mid_game", &screen);
+}
+
+#[test]
+fn characters_menu_matches_golden_file() {
+    let mut state = GameState::with_seed(SEED);
+    state.select_skin(1);
+    let mut screen = screen(&state);
+
+    render_characters_menu(&state, &mut screen);
+    assert_golden("characters", &screen);
+}
+
+#[test]
+fn unreadable_high_scores_are_reported_on_the_high_scores_screen() {
+    // A relative path keeps the reported error the same on every machine.
+    let path = Path::new("target/corrupt_high_scores.toml");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "entries = 3").unwrap();
+
+    let mut state = GameState::with_seed(SEED);
+    state.load_high_scores(path.to_path_buf());
+    assert!(state.high_scores().entries().is_empty());
+
+    state.set_mode(GameMode::GameOver);
+    state.show_high_scores();
+    let mut screen = screen(&state);
+    render_high_scores_menu(&state, &mut screen);
+    assert_golden("high_scores_load_error", &screen);
+
+    state.close_high_scores();
+    assert_eq!(state.mode(), GameMode::GameOver);
+}
@@ -0,0 +1,32 @@
+//! Every finished run must keep its own replay file.
+
+use flappycheems::game::{GameState, Replay, TickOutcome};
+use std::fs;
+use std::path::PathBuf;
+
+const SEED: u64 = 11;
+
+/// Plays the current run without flapping until it ends.
+fn crash(state: &mut GameState) {
+    while state.step() != TickOutcome::GameOver {}
+}
+
+#[test]
+fn back_to_back_runs_of_one_seed_keep_both_replays() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("back_to_back_replays");
+    fs::remove_dir_all(&dir).ok();
+
+    let mut state = GameState::with_seed(SEED);
+    state.set_replay_dir(Some(dir.clone()));
+    state.reset_game();
+    crash(&mut state);
+    state.reset_game();
+    crash(&mut state);
+
+    let replays: Vec<Replay> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| Replay::load(entry.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(replays.len(), 2);
+    assert!(replays.iter().all(|replay| replay.seed == SEED));
+}